@@ -0,0 +1,214 @@
+// audit.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module defines the audit trail of the application. Every client
+//! and employee mutation made through the ClientHandler / EmployeeHandler
+//! records an AuditEntry: who made the change, what it was, which client
+//! or employee it was made to, the values before and after the change, and
+//! when it happened. Entries are written to the `audit_log` table in the
+//! same transaction as the change itself.
+
+// imports the Utc clock from the chrono crate
+use chrono::Utc;
+// imports the json! macro from the serde_json crate
+use serde_json::json;
+
+// imports the Session & failed login record from the auth module
+use crate::auth::{LoginAttempts, Session};
+// imports the Client & Employee structs from the firm_models module
+use crate::firm_models::{Client, Employee};
+
+//
+// ********************************************
+// audit.rs module definitions begin here:
+// ********************************************
+//
+
+/// represents the kind of mutation recorded by an audit entry
+///
+/// stored by name in the `action` column of the audit_log table.
+///
+///# Variants
+///
+///* `NewClient` - a client was added, target is the client id
+///* `UpdateClient` - a client was modified, target is the client id
+///* `RemoveClient` - a client was removed, target is the client id
+///* `AddEmployee` - an employee was added, target is the employee id
+///* `ModifyEmployee` - an employee was modified, target is the employee id
+///* `DeleteEmployee` - an employee was deleted, target is the employee id
+///* `UnlockEmployee` - an employee's failed logins were cleared, target is the employee id
+///* `ChangePassword` - an employee changed their own password, target is the employee id
+///* `RehashPassword` - an employee's password hash was upgraded to the HashPolicy when
+///  they signed in, target is the employee id
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditAction {
+    NewClient,
+    UpdateClient,
+    RemoveClient,
+    AddEmployee,
+    ModifyEmployee,
+    DeleteEmployee,
+    UnlockEmployee,
+    ChangePassword,
+    RehashPassword,
+}
+
+impl AuditAction {
+    /// returns the name the action is stored as
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::NewClient => "new_client",
+            Self::UpdateClient => "update_client",
+            Self::RemoveClient => "remove_client",
+            Self::AddEmployee => "add_employee",
+            Self::ModifyEmployee => "modify_employee",
+            Self::DeleteEmployee => "delete_employee",
+            Self::UnlockEmployee => "unlock_employee",
+            Self::ChangePassword => "change_password",
+            Self::RehashPassword => "rehash_password",
+        }
+    }
+}
+
+/// represents a single entry of the audit trail
+///
+///# Fields
+///
+///* `occurred_at` - i64, unix timestamp (seconds, UTC) of the change
+///* `employee_id` - i32, id of the signed in employee who made the change
+///* `action` - AuditAction, the kind of change
+///* `target_id` - i32, id of the client / employee that was changed
+///* `before` - JSON of the values before the change, None for additions
+///* `after` - JSON of the values after the change, None for removals
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct AuditEntry {
+    pub occurred_at: i64,
+    pub employee_id: i32,
+    pub action: AuditAction,
+    pub target_id: i32,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl AuditEntry {
+    /// Creates a new audit entry, timestamped with the current time
+    ///
+    ///# Arguments
+    ///
+    ///* 'session' - the signed in employee who made the change
+    ///* 'action' - the kind of change
+    ///* 'target_id' - id of the client / employee that was changed
+    ///* 'before' / 'after' - JSON snapshots of the values, see client_snapshot
+    ///  and employee_snapshot
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the new AuditEntry
+    ///
+    pub fn new(
+        session: &Session,
+        action: AuditAction,
+        target_id: i32,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> Self {
+        Self::by_employee(session.get_employee_id(), action, target_id, before, after)
+    }
+
+    /// Creates a new audit entry for a change made without a Session
+    ///
+    /// used while signing in, before the employee's Session exists.
+    ///
+    ///# Arguments
+    ///
+    ///* 'employee_id' - id of the employee who made the change
+    ///* 'action', 'target_id', 'before' / 'after' - as for AuditEntry::new
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the new AuditEntry
+    ///
+    pub fn by_employee(
+        employee_id: i32,
+        action: AuditAction,
+        target_id: i32,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> Self {
+        AuditEntry {
+            occurred_at: Utc::now().timestamp(),
+            employee_id,
+            action,
+            target_id,
+            before,
+            after,
+        }
+    }
+}
+
+/// returns the JSON snapshot of a client's audited values
+///
+///# Arguments
+///
+///* 'client' - reference to the Client
+///
+///# Returns
+///
+///* 'String' - JSON object of the client name, service and assigned employee
+///
+pub fn client_snapshot(client: &Client) -> String {
+    json!({
+        "client_name": client.get_client_name(),
+        "client_service": client.get_client_service(),
+        "assigned_employee": client.get_asn_employee(),
+    })
+    .to_string()
+}
+
+/// returns the JSON snapshot of an employee's audited values
+///
+/// the password hash is never recorded. When the password changed
+/// between two snapshots, the after snapshot notes it instead.
+///
+///# Arguments
+///
+///* 'employee' - reference to the Employee
+///* 'password_changed' - true when this change replaced the password
+///
+///# Returns
+///
+///* 'String' - JSON object of the employee name and role
+///
+pub fn employee_snapshot(employee: &Employee, password_changed: bool) -> String {
+    let mut snapshot = json!({
+        "employee_name": employee.get_employee_name(),
+        "employee_role": employee.get_employee_role().name(),
+    });
+    if password_changed {
+        snapshot["password_changed"] = json!(true);
+    }
+    snapshot.to_string()
+}
+
+/// returns the JSON snapshot of an employee's failed login record
+///
+///# Arguments
+///
+///* 'attempts' - reference to the LoginAttempts
+///
+///# Returns
+///
+///* 'String' - JSON object of the failed attempts and lockout end
+///
+pub fn login_attempts_snapshot(attempts: &LoginAttempts) -> String {
+    json!({
+        "failed_attempts": attempts.failed_attempts,
+        "locked_until": attempts.locked_until,
+    })
+    .to_string()
+}
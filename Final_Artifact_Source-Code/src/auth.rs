@@ -8,21 +8,33 @@
 //! functions that implement the argon2 crate. This is used to provide
 //! the validation of login credentials.
 
-// imports the Config struct from the argon2 crate for hashing config
-use argon2::Config;
+// imports the Config struct & Argon2 variants from the argon2 crate for hashing config
+use argon2::{Config, Variant, Version};
+// imports the Utc clock from the chrono crate
+use chrono::Utc;
+// imports the warn logging macro from the log crate
+use log::warn;
 // imports the Rng trait from rand crate to use in salt generation
 use rand::Rng;
-// imports process module from std library
-use std::process;
+// imports the TryFrom trait from the standard library, for config integer conversion
+use std::convert::TryFrom;
+// imports the OnceLock cell from the standard library, holds the installed hash policy
+use std::sync::OnceLock;
+// imports the Duration & Instant structs from the standard library, for session inactivity
+use std::time::{Duration, Instant};
 
 //imports all public items from the operation_handlers module
 use crate::operation_handlers::EmployeeHandler;
+// imports the timestamp formatting function from the client_history module
+use crate::client_history::format_timestamp;
 // imports necessary errors from errors module
 use crate::errors::ApplicationError;
+// imports the Employee struct & roles from the firm_models module
+use crate::firm_models::{Employee, EmployeeRole};
 // imports all public items from the database module
-use crate::database::DatabaseManager;
+use crate::database::{load_config, DatabaseManager};
 // imports all public items from the util module
-use crate::util::{get_integer_input, get_string_input};
+use crate::util::{get_integer_input, get_password_input};
 
 //
 // ********************************************
@@ -30,6 +42,437 @@ use crate::util::{get_integer_input, get_string_input};
 // ********************************************
 //
 
+/// the hash policy installed at startup, see HashPolicy::install
+static HASH_POLICY: OnceLock<HashPolicy> = OnceLock::new();
+/// the password policy installed at startup, see PasswordPolicy::install
+static PASSWORD_POLICY: OnceLock<PasswordPolicy> = OnceLock::new();
+
+/// commonly used passwords that are always rejected, extended by
+/// `password_policy.denylist` in config.toml
+const DEFAULT_DENYLIST: [&str; 8] = [
+    "password",
+    "password1",
+    "password123",
+    "123456789",
+    "1234567890",
+    "qwertyuiop",
+    "letmein123",
+    "welcome123",
+];
+
+/// represents the Argon2 parameters new password hashes are created with
+///
+/// read from the `[password_hash]` table of config.toml. Any key that
+/// is not set keeps the argon2 crate's default (argon2id, 19 MiB, t=2, p=1).
+/// Stored hashes created with weaker parameters are re-hashed on the
+/// employee's next successful login, see Authenticator::authenticate.
+///
+///# Fields
+///
+///* `variant` - Argon2 variant, `password_hash.variant` (argon2d / argon2i / argon2id)
+///* `memory_cost` - memory in KiB, `password_hash.memory_cost`
+///* `time_cost` - number of passes, `password_hash.time_cost`
+///* `parallelism` - number of lanes, `password_hash.parallelism`
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct HashPolicy {
+    pub variant: Variant,
+    pub memory_cost: u32,
+    pub time_cost: u32,
+    pub parallelism: u32,
+}
+
+impl Default for HashPolicy {
+    /// the argon2 crate's default parameters
+    fn default() -> Self {
+        let config = Config::default();
+        HashPolicy {
+            variant: config.variant,
+            memory_cost: config.mem_cost,
+            time_cost: config.time_cost,
+            parallelism: config.lanes,
+        }
+    }
+}
+
+impl HashPolicy {
+    /// Loads the hash policy from config.toml
+    ///
+    /// a missing config.toml, or missing keys, keep the default parameters.
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Self, ApplicationError>' - the policy, or ConfigError for an
+    ///  unknown variant / parameters argon2 would reject
+    ///
+    pub fn load() -> Result<Self, ApplicationError> {
+        let config = load_config(false)?;
+        let mut policy = HashPolicy::default();
+
+        if let Ok(variant) = config.get_string("password_hash.variant") {
+            policy.variant = Variant::from_str(&variant).map_err(|_| {
+                ApplicationError::ConfigError(format!(
+                    "Unknown password_hash.variant: {}, use argon2d, argon2i or argon2id",
+                    variant
+                ))
+            })?;
+        }
+        let cost = |key: &str, default: u32| -> Result<u32, ApplicationError> {
+            match config.get_int(key) {
+                Ok(value) => u32::try_from(value)
+                    .ok()
+                    .filter(|&value| value > 0)
+                    .ok_or_else(|| {
+                        ApplicationError::ConfigError(format!("{} must be a positive integer", key))
+                    }),
+                Err(_) => Ok(default),
+            }
+        };
+        policy.memory_cost = cost("password_hash.memory_cost", policy.memory_cost)?;
+        policy.time_cost = cost("password_hash.time_cost", policy.time_cost)?;
+        policy.parallelism = cost("password_hash.parallelism", policy.parallelism)?;
+
+        // argon2 needs at least 8 KiB of memory per lane
+        if policy.memory_cost < 8 * policy.parallelism {
+            return Err(ApplicationError::ConfigError(format!(
+                "password_hash.memory_cost must be at least 8 KiB per lane ({} KiB)",
+                8 * policy.parallelism
+            )));
+        }
+        Ok(policy)
+    }
+
+    /// Installs the policy used by Authenticator::hash_password
+    ///
+    /// called once at startup, before any password is hashed. Later
+    /// calls are ignored, the first installed policy is kept.
+    pub fn install(self) {
+        if HASH_POLICY.set(self).is_err() {
+            warn!("Password hash policy was already installed, keeping the first one.");
+        }
+    }
+
+    /// returns the installed policy, or the default policy when none was installed
+    pub fn current() -> HashPolicy {
+        HASH_POLICY.get().cloned().unwrap_or_default()
+    }
+
+    /// returns the argon2 Config for hashing with this policy
+    pub fn config(&self) -> Config<'static> {
+        Config {
+            variant: self.variant,
+            mem_cost: self.memory_cost,
+            time_cost: self.time_cost,
+            lanes: self.parallelism,
+            ..Config::default()
+        }
+    }
+
+    /// Checks whether an encoded hash was created with weaker parameters
+    ///
+    /// reads the parameters from the encoded hash, e.g.
+    /// `$argon2id$v=19$m=19456,t=2,p=1$<salt>$<hash>`.
+    ///
+    ///# Arguments
+    ///
+    ///* 'encoded' - the stored, encoded hash
+    ///
+    ///# Returns
+    ///
+    ///* 'bool' - true when the variant differs from the policy, the version is
+    ///  older, any cost is lower than the policy, or the hash cannot be read
+    ///
+    pub fn needs_rehash(&self, encoded: &str) -> bool {
+        let mut sections = encoded.split('$').skip(1);
+        let variant = match sections.next().map(Variant::from_str) {
+            Some(Ok(variant)) => variant,
+            _ => return true,
+        };
+        // hashes from before version 1.3 have no v= section
+        let mut version = Version::Version10.as_u32();
+        let mut params = sections.next().unwrap_or_default();
+        if let Some(value) = params.strip_prefix("v=") {
+            version = value.parse().unwrap_or(0);
+            params = sections.next().unwrap_or_default();
+        }
+
+        let (mut memory_cost, mut time_cost, mut parallelism) = (0, 0, 0);
+        for param in params.split(',') {
+            let (key, value) = param.split_at(param.find('=').unwrap_or(0));
+            let value: u32 = value.trim_start_matches('=').parse().unwrap_or(0);
+            match key {
+                "m" => memory_cost = value,
+                "t" => time_cost = value,
+                "p" => parallelism = value,
+                _ => {}
+            }
+        }
+
+        variant != self.variant
+            || version < Version::Version13.as_u32()
+            || memory_cost < self.memory_cost
+            || time_cost < self.time_cost
+            || parallelism < self.parallelism
+    }
+}
+
+/// represents the requirements new passwords must meet
+///
+/// read from the `[password_policy]` table of config.toml. Any key that
+/// is not set keeps its default. Only checked when a password is set,
+/// existing passwords keep working until they are changed.
+///
+///# Fields
+///
+///* `min_length` - minimum number of characters, `password_policy.min_length` (10)
+///* `require_lowercase` - at least one lowercase letter, `password_policy.require_lowercase` (true)
+///* `require_uppercase` - at least one uppercase letter, `password_policy.require_uppercase` (true)
+///* `require_digit` - at least one digit, `password_policy.require_digit` (true)
+///* `require_symbol` - at least one other character, `password_policy.require_symbol` (false)
+///* `denylist` - rejected passwords, compared ignoring case. DEFAULT_DENYLIST plus
+///  `password_policy.denylist`
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub require_lowercase: bool,
+    pub require_uppercase: bool,
+    pub require_digit: bool,
+    pub require_symbol: bool,
+    pub denylist: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        PasswordPolicy {
+            min_length: 10,
+            require_lowercase: true,
+            require_uppercase: true,
+            require_digit: true,
+            require_symbol: false,
+            denylist: DEFAULT_DENYLIST.iter().map(|entry| entry.to_string()).collect(),
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Loads the password policy from config.toml
+    ///
+    /// a missing config.toml, or missing keys, keep the default requirements.
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Self, ApplicationError>' - the policy, or ConfigError for an invalid value
+    ///
+    pub fn load() -> Result<Self, ApplicationError> {
+        let config = load_config(false)?;
+        let mut policy = PasswordPolicy::default();
+
+        if let Ok(min_length) = config.get_int("password_policy.min_length") {
+            policy.min_length = usize::try_from(min_length)
+                .ok()
+                .filter(|&min_length| min_length > 0)
+                .ok_or_else(|| {
+                    ApplicationError::ConfigError(
+                        "password_policy.min_length must be a positive integer".to_string(),
+                    )
+                })?;
+        }
+        for (key, requirement) in [
+            ("password_policy.require_lowercase", &mut policy.require_lowercase),
+            ("password_policy.require_uppercase", &mut policy.require_uppercase),
+            ("password_policy.require_digit", &mut policy.require_digit),
+            ("password_policy.require_symbol", &mut policy.require_symbol),
+        ] {
+            if let Ok(value) = config.get_bool(key) {
+                *requirement = value;
+            }
+        }
+        if let Ok(entries) = config.get_array("password_policy.denylist") {
+            for entry in entries {
+                policy.denylist.push(entry.into_string()?);
+            }
+        }
+        Ok(policy)
+    }
+
+    /// Installs the policy checked by PasswordPolicy::current
+    ///
+    /// called once at startup. Later calls are ignored, the first
+    /// installed policy is kept.
+    pub fn install(self) {
+        if PASSWORD_POLICY.set(self).is_err() {
+            warn!("Password policy was already installed, keeping the first one.");
+        }
+    }
+
+    /// returns the installed policy, or the default policy when none was installed
+    pub fn current() -> PasswordPolicy {
+        PASSWORD_POLICY.get().cloned().unwrap_or_default()
+    }
+
+    /// Checks a new password against the policy
+    ///
+    ///# Arguments
+    ///
+    ///* 'password' - the new password
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok when every requirement is met, otherwise
+    ///  InputError listing each requirement the password does not meet
+    ///
+    pub fn validate(&self, password: &str) -> Result<(), ApplicationError> {
+        let mut unmet = Vec::new();
+        if password.chars().count() < self.min_length {
+            unmet.push(format!("at least {} characters", self.min_length));
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            unmet.push("a lowercase letter".to_string());
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            unmet.push("an uppercase letter".to_string());
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            unmet.push("a digit".to_string());
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            unmet.push("a symbol, such as !@#$%".to_string());
+        }
+        if self
+            .denylist
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(password))
+        {
+            unmet.push("not a commonly used password".to_string());
+        }
+
+        if unmet.is_empty() {
+            Ok(())
+        } else {
+            Err(ApplicationError::InputError(format!(
+                "The password must have {}",
+                unmet.join(", ")
+            )))
+        }
+    }
+}
+
+/// Prompts for a new password until one meets the PasswordPolicy
+///
+/// the password is entered twice, and must match.
+///
+///# Returns
+///
+///* 'Result<String, ApplicationError>' - the new password, or an input error
+///
+pub fn prompt_new_password() -> Result<String, ApplicationError> {
+    let policy = PasswordPolicy::current();
+    loop {
+        let password = get_password_input("Enter the new password")?;
+        if let Err(e) = policy.validate(&password) {
+            println!("\n{}. Please try again.", e);
+            continue;
+        }
+        if get_password_input("Enter the new password again")? != password {
+            println!("\nThe passwords do not match. Please try again.");
+            continue;
+        }
+        return Ok(password);
+    }
+}
+
+/// consecutive failed logins after which an employee is locked out
+pub const LOCKOUT_THRESHOLD: i32 = 5;
+/// length of the first lockout, in seconds. Doubles with every further failure
+pub const LOCKOUT_BASE_SECONDS: i64 = 60;
+/// longest lockout, in seconds (one day)
+pub const LOCKOUT_MAX_SECONDS: i64 = 24 * 60 * 60;
+/// minutes without input after which the menu is locked, unless set in config.toml
+pub const DEFAULT_IDLE_TIMEOUT_MINUTES: i64 = 15;
+
+/// represents the failed login record of an employee
+///
+/// persisted in the `login_attempts` table, so that failed attempts
+/// are not reset by restarting the application. Removed by a
+/// successful login, or by an admin unlocking the employee.
+///
+///# Fields
+///
+///* `employee_id` - i32, id of the employee the attempts were made for
+///* `failed_attempts` - i32, consecutive failed attempts
+///* `last_failed_at` - i64, unix timestamp (seconds, UTC) of the last failed attempt
+///* `locked_until` - i64, unix timestamp the employee is locked out until, 0 when not locked
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoginAttempts {
+    pub employee_id: i32,
+    pub failed_attempts: i32,
+    pub last_failed_at: i64,
+    pub locked_until: i64,
+}
+
+impl LoginAttempts {
+    /// Creates an empty failed login record for an employee
+    ///
+    ///# Arguments
+    ///
+    ///* 'employee_id' - id of the employee
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the new LoginAttempts, with no failed attempts
+    ///
+    pub fn new(employee_id: i32) -> Self {
+        LoginAttempts {
+            employee_id,
+            failed_attempts: 0,
+            last_failed_at: 0,
+            locked_until: 0,
+        }
+    }
+
+    /// returns true while the employee is locked out at the provided time
+    pub fn is_locked(&self, now: i64) -> bool {
+        self.locked_until > now
+    }
+
+    /// Records a failed attempt, and locks the employee out once
+    /// LOCKOUT_THRESHOLD consecutive attempts have failed
+    ///
+    /// the first lockout lasts LOCKOUT_BASE_SECONDS, and every further
+    /// failed attempt doubles it, up to LOCKOUT_MAX_SECONDS.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///* 'now' - unix timestamp of the failed attempt
+    ///
+    pub fn record_failure(&mut self, now: i64) {
+        self.failed_attempts += 1;
+        self.last_failed_at = now;
+        self.apply_lockout(now);
+    }
+
+    /// Locks the employee out when the failed attempts have reached LOCKOUT_THRESHOLD
+    ///
+    /// used by record_failure, and by the database backends after they
+    /// increment the failed attempts themselves.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///* 'now' - unix timestamp of the last failed attempt
+    ///
+    pub fn apply_lockout(&mut self, now: i64) {
+        if self.failed_attempts >= LOCKOUT_THRESHOLD {
+            let doublings = (self.failed_attempts - LOCKOUT_THRESHOLD).min(16) as u32;
+            let lockout = (LOCKOUT_BASE_SECONDS << doublings).min(LOCKOUT_MAX_SECONDS);
+            self.locked_until = now + lockout;
+        }
+    }
+}
+
 /// struct represents the authentication process of the system
 ///
 /// contains the fields necessary to process authentication attempts
@@ -66,7 +509,8 @@ impl Authenticator {
     /// employee's login attempt
     ///
     /// accepts employee's details, & attempts to validate their login attempt.
-    /// also tracks their qty of login attempts, will exit if max value reached.
+    /// also tracks their qty of login attempts in this session, and the
+    /// employee's persisted failed attempts, see LoginAttempts.
     ///
     ///# Arguments
     ///
@@ -79,18 +523,22 @@ impl Authenticator {
     ///
     ///* 'Result<Ok(true)>' -  Authentication succeeded in validating login attempt
     ///* 'Result<Ok(false)>' - login attempt failed (bad pass / id value)
+    ///* 'Result<AuthenticationError>' - max attempts reached, or the employee is locked out
     ///* 'Result<DatabaseError>' - an error occurred attempting to access database
     ///
     ///# Behavior
     /// 1. checks that attempts has not reached maximum allowed
-    ///     if max reached, immediately terminates then application (exits)
+    ///    if max reached, returns AuthenticationError
     /// 2. increments attempt count
-    /// 3. attempts to retrieve stored hash for provided id number
-    /// 4. hash found: validates stored hash against hashed input password
-    ///     hashes match: return Ok(true)
-    ///     hashes dont match: return Ok(false)
-    /// 5. hash not found: return Ok(false) (no matching employee)
-    /// 6. return the result of authentication / validation attempt
+    /// 3. checks the employee's persisted failed attempts
+    ///    if locked out, returns AuthenticationError without checking the password
+    /// 4. attempts to retrieve stored hash for provided id number
+    /// 5. hash found: validates stored hash against hashed input password
+    ///    hashes match: clears the failed attempts, re-hashes the password when
+    ///    the stored hash is weaker than the HashPolicy, return Ok(true)
+    ///    hashes dont match: records the failed attempt, return Ok(false)
+    /// 6. hash not found: return Ok(false) (no matching employee)
+    /// 7. return the result of authentication / validation attempt
     ///
     pub fn authenticate(
         &mut self,
@@ -99,26 +547,93 @@ impl Authenticator {
         password: &str,
     ) -> Result<bool, ApplicationError> {
         if self.current_attempts >= self.max_attempts {
-            println!("Maximum attempts reached, exiting program.");
-            process::exit(1);
+            return Err(ApplicationError::AuthenticationError(
+                "Maximum attempts reached".to_string(),
+            ));
         }
 
         // increment attempts
         self.current_attempts += 1;
 
+        let now = Utc::now().timestamp();
+        let attempts = employee_handler.get_login_attempts(employee_id)?;
+        if let Some(ref attempts) = attempts {
+            if attempts.is_locked(now) {
+                return Err(ApplicationError::AuthenticationError(format!(
+                    "Employee: {} is locked out until {} UTC, or until an admin unlocks it",
+                    employee_id,
+                    format_timestamp(attempts.locked_until)
+                )));
+            }
+        }
+
         // calls dbmanager get_emp_hash fn
         match employee_handler.get_employee_hash(employee_id)? {
             Some(stored_hash) => {
-                Ok(argon2::verify_encoded(&stored_hash, password.as_bytes()).unwrap_or(false))
+                if Self::verify_password(&stored_hash, password) {
+                    if attempts.is_some() {
+                        employee_handler.clear_login_attempts(employee_id)?;
+                    }
+                    if HashPolicy::current().needs_rehash(&stored_hash) {
+                        // the login still succeeds, the rehash is retried on the next one
+                        if let Err(e) = Self::rehash_password(employee_handler, employee_id, password) {
+                            warn!("Could not re-hash the password of Employee: {}: {}", employee_id, e);
+                        }
+                    }
+                    Ok(true)
+                } else {
+                    // only recorded for existing employees, unknown ids are not persisted
+                    employee_handler.record_login_failure(employee_id, now)?;
+                    Ok(false)
+                }
             }
             None => Ok(false),
         }
     }
+    /// checks a password against a stored, encoded hash
+    ///
+    ///# Arguments
+    ///
+    ///* 'stored_hash' - the employee's stored hash
+    ///* 'password' - reference to input password string
+    ///
+    ///# Returns
+    ///
+    ///* 'bool' - true when the password matches, false otherwise or for an unreadable hash
+    ///
+    pub fn verify_password(stored_hash: &str, password: &str) -> bool {
+        argon2::verify_encoded(stored_hash, password.as_bytes()).unwrap_or(false)
+    }
+
+    /// re-hashes an employee's password with the current HashPolicy
+    ///
+    /// saved through EmployeeHandler::update_password_hash, which records
+    /// the rehash under its own audit action.
+    ///
+    ///# Arguments
+    ///
+    ///* 'employee_handler' - mutable reference to an implementation of EmployeeHandler
+    ///* 'employee_id' - i32 integer value, employee_id of the authenticated employee
+    ///* 'password' - reference to the verified input password string
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok once saved, or the hashing / database error
+    ///
+    fn rehash_password(
+        employee_handler: &mut EmployeeHandler,
+        employee_id: i32,
+        password: &str,
+    ) -> Result<(), ApplicationError> {
+        let hash = Self::hash_password(password)?;
+        employee_handler.update_password_hash(employee_id, &hash)
+    }
+
     /// function used to hash user input password strings
     ///
     /// takes a user input string password, and processes it
     /// using argon2 hash_encoded. This generates a salt, and
-    /// hash uses default argon2 config.
+    /// hash uses the installed HashPolicy.
     ///
     ///# Arguments
     ///
@@ -130,7 +645,7 @@ impl Authenticator {
     ///* 'Result<argon2::Error' - returns error on failure
     ///
     pub fn hash_password(password: &str) -> Result<String, ApplicationError> {
-        let config = Config::default();
+        let config = HashPolicy::current().config();
         argon2::hash_encoded(
             password.as_bytes(),
             &Authenticator::generate_salt(),
@@ -154,11 +669,163 @@ impl Authenticator {
         rand::thread_rng().gen::<[u8; 16]>()
     }
 }
+/// represents the identity of an authenticated employee
+///
+/// returned by a successful login, and passed to the handler
+/// operations so that they can be attributed to, and permission
+/// checked against, the signed in employee. Does not hold the
+/// employee's password hash.
+///
+///# Fields
+///
+///* `employee_id` - i32 integer value, id of the signed in employee
+///* `employee_name` - String, name of the signed in employee
+///* `role` - EmployeeRole of the signed in employee
+///* `last_activity` - time of the employee's last input, see Session::touch
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Session {
+    employee_id: i32,
+    employee_name: String,
+    role: EmployeeRole,
+    last_activity: Instant,
+}
+
+impl Session {
+    /// Creates the session of an authenticated employee
+    ///
+    ///# Arguments
+    ///
+    ///* 'employee' - reference to the authenticated Employee
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the new Session
+    ///
+    pub fn new(employee: &Employee) -> Self {
+        Session {
+            employee_id: employee.get_employee_id(),
+            employee_name: employee.get_employee_name().to_string(),
+            role: employee.get_employee_role(),
+            last_activity: Instant::now(),
+        }
+    }
+
+    /// records input from the signed in employee, restarting the idle time
+    pub fn touch(&mut self) {
+        self.last_activity = Instant::now();
+    }
+
+    /// returns true when the employee has been idle for longer than the timeout
+    ///
+    ///# Arguments
+    ///
+    ///* 'idle_timeout' - the idle timeout, None when sessions never time out
+    ///
+    pub fn is_idle(&self, idle_timeout: Option<Duration>) -> bool {
+        idle_timeout.is_some_and(|timeout| self.last_activity.elapsed() > timeout)
+    }
+
+    /// returns the id of the signed in employee
+    pub fn get_employee_id(&self) -> i32 {
+        self.employee_id
+    }
+
+    /// returns the name of the signed in employee
+    pub fn get_employee_name(&self) -> &str {
+        &self.employee_name
+    }
+
+    /// returns the role of the signed in employee
+    pub fn get_role(&self) -> EmployeeRole {
+        self.role
+    }
+
+    /// returns the prompt line identifying the signed in employee,
+    /// e.g. "Logged in as #7 Jane"
+    pub fn prompt(&self) -> String {
+        format!("Logged in as #{} {}", self.employee_id, self.employee_name)
+    }
+}
+
+/// Loads the session idle timeout from config.toml
+///
+/// read from `session.idle_timeout_minutes`, 15 minutes when not set.
+/// 0 disables the timeout.
+///
+///# Returns
+///
+///* 'Result<Option<Duration>, ApplicationError>' - the timeout, None when disabled,
+///  or ConfigError for a negative value
+///
+pub fn load_idle_timeout() -> Result<Option<Duration>, ApplicationError> {
+    let minutes = load_config(false)?
+        .get_int("session.idle_timeout_minutes")
+        .unwrap_or(DEFAULT_IDLE_TIMEOUT_MINUTES);
+    match u64::try_from(minutes) {
+        Ok(0) => Ok(None),
+        Ok(minutes) => Ok(Some(Duration::from_secs(minutes * 60))),
+        Err(_) => Err(ApplicationError::ConfigError(
+            "session.idle_timeout_minutes must be 0 or a positive integer".to_string(),
+        )),
+    }
+}
+
+/// function to re-authenticate the signed in employee after a timeout
+///
+/// prompts for the password of the session's employee, up to
+/// Authenticator::max_attempts times. Each attempt goes through the
+/// Authenticator, so failed attempts count towards the employee's lockout.
+///
+///# Arguments
+///
+///* 'employee_handler' - mutable reference to an implementation of EmployeeHandler
+///* 'session' - mutable reference to the timed out Session
+///
+///# Returns
+///
+///* 'Ok(true)' - the employee re-authenticated, the session continues.
+///* 'Ok(false)' - every attempt failed, or the employee is locked out.
+///* 'Err(ApplicationError)' - input or database error
+///
+pub fn reauthenticate(
+    employee_handler: &mut EmployeeHandler,
+    session: &mut Session,
+) -> Result<bool, ApplicationError> {
+    let mut authenticator = Authenticator::new();
+    println!(
+        "\nSession locked after inactivity. {}, please enter your password to continue.",
+        session.get_employee_name()
+    );
+    while authenticator.current_attempts < authenticator.max_attempts {
+        let password = get_password_input("Password")?;
+        match authenticator.authenticate(employee_handler, session.get_employee_id(), &password) {
+            Ok(true) => {
+                session.touch();
+                return Ok(true);
+            }
+            Ok(false) => println!("\nAuthentication attempt failed."),
+            Err(ApplicationError::AuthenticationError(reason)) => {
+                println!("\nAuthentication attempt failed: {}", reason);
+                return Ok(false);
+            }
+            Err(e) => return Err(e),
+        }
+        println!(
+            "You have used {} of {} attempts.",
+            authenticator.current_attempts, authenticator.max_attempts
+        );
+    }
+    Ok(false)
+}
+
 /// function to manage the login process
 ///
 /// loops 0 - max_attepmts times, accepting user input.
-/// upon valid auth credentials provided, returns true.
-/// else max_attempts reached return false.
+/// upon valid auth credentials provided, returns the Session of the
+/// authenticated employee. else max_attempts reached return None.
+/// An attempt for a locked out employee is reported, and counts
+/// towards max_attempts.
 ///
 ///
 ///# Arguments
@@ -167,8 +834,8 @@ impl Authenticator {
 ///
 ///# Returns
 ///
-///* 'Ok(true)' - when operation is successful.
-///* 'Ok(false)' - when operation fails.
+///* 'Ok(Some(Session))' - when operation is successful, the signed in employee's session.
+///* 'Ok(None)' - when operation fails.
 ///* 'Err(OperationError)' - would likely return a OperationError::DatabaseError
 ///
 ///# Errors
@@ -177,7 +844,9 @@ impl Authenticator {
 /// the provided client_id does not match an existing client.
 /// Could also return one of the other db errors as defined in database.rs
 ///
-pub fn login_handler(database: &mut dyn DatabaseManager) -> Result<bool, ApplicationError> {
+pub fn login_handler(
+    database: &mut dyn DatabaseManager,
+) -> Result<Option<Session>, ApplicationError> {
     let mut employee_handler = EmployeeHandler::new(database.clone_box())?;
     let mut authenticator = Authenticator::new();
 
@@ -187,27 +856,32 @@ pub fn login_handler(database: &mut dyn DatabaseManager) -> Result<bool, Applica
     loop {
         println!("\nPlease enter your Employee ID number: ");
         let employee_id = get_integer_input()?; // to prop error if needed
-        println!("\nPlease enter your Employee password: ");
-        let password = get_string_input()?; // to prop error if needed
+        // not echoed on a terminal, so the password is not shown on shared screens
+        let password = get_password_input("\nPlease enter your Employee password")?; // to prop error if needed
 
-        match authenticator.authenticate(&mut employee_handler, employee_id, &password)? {
-            true => {
+        match authenticator.authenticate(&mut employee_handler, employee_id, &password) {
+            Ok(true) => {
                 println!("\nEmployee successfully authenticated.");
-                return Ok(true);
+                // the hash was found, so the employee exists
+                return Ok(employee_handler
+                    .get_employee(employee_id)?
+                    .map(|employee| Session::new(&employee)));
             }
-            false => {
-                println!(
-                    "\nAuthentication attempt failed. You have used {} of {} attempts. \
-                    Please try again.",
-                    authenticator.current_attempts, authenticator.max_attempts
-                );
-
-                // upon reaching max attempts, returns false / ends program
-                if authenticator.current_attempts >= authenticator.max_attempts {
-                    println!("\nYou have reached the maximum allowed login attempts. Goodbye.");
-                    return Ok(false);
-                }
+            Ok(false) => println!("\nAuthentication attempt failed."),
+            Err(ApplicationError::AuthenticationError(reason)) => {
+                println!("\nAuthentication attempt failed: {}", reason);
             }
+            Err(e) => return Err(e),
+        }
+        println!(
+            "You have used {} of {} attempts. Please try again.",
+            authenticator.current_attempts, authenticator.max_attempts
+        );
+
+        // upon reaching max attempts, returns None / ends program
+        if authenticator.current_attempts >= authenticator.max_attempts {
+            println!("\nYou have reached the maximum allowed login attempts. Goodbye.");
+            return Ok(None);
         }
     }
 }
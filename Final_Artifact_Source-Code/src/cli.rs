@@ -0,0 +1,418 @@
+// cli.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module defines the non-interactive command line interface.
+//! Subcommands run a single operation through the ClientHandler /
+//! EmployeeHandler and exit, without the interactive menu, so that
+//! bulk changes can be scripted. The employee ID is taken from the
+//! --employee-id argument or the CMS_EMPLOYEE_ID environment variable.
+//! Passwords are never taken as arguments, where they would be visible
+//! in the process list & shell history. They are read from the
+//! CMS_PASSWORD / CMS_NEW_EMPLOYEE_PASSWORD environment variables, or
+//! prompted for on stdin when those are not set.
+//! Uses the [clap](https://docs.rs/clap/latest/clap/) crate.
+
+// imports the env module from the standard library
+use std::env;
+// imports the Path / PathBuf structs from the standard library path module
+use std::path::PathBuf;
+
+// imports the argument parsing derives from the clap crate
+use clap::{Args, Parser, Subcommand};
+
+// imports the Authenticator & Session from the auth module
+use crate::auth::{prompt_new_password, Authenticator, PasswordPolicy, Session};
+// imports the client book import / export items from the client_books module
+use crate::client_books::{collect_client_book, export_clients, import_clients, ExportFormat};
+// imports the client search filter from the client_cache module
+use crate::client_cache::ClientFilter;
+// imports all public items from the database module
+use crate::database::DatabaseManager;
+// imports necessary errors from errors module
+use crate::errors::ApplicationError;
+// imports the Employee struct & roles from the firm_models module
+use crate::firm_models::{Employee, EmployeeRole};
+// imports the service choices from the menu module
+use crate::menu::ClientServiceChoice;
+// imports the client & employee handlers from the operation_handlers module
+use crate::operation_handlers::{ClientHandler, EmployeeHandler};
+// imports the password prompt from the util module
+use crate::util::get_password_input;
+
+//
+// ********************************************
+// cli.rs module definitions begin here:
+// ********************************************
+//
+
+/// represents the parsed command line of the application
+///
+/// without a subcommand, the application starts the interactive
+/// login and main menu.
+///
+///# Fields
+///
+///* `employee_id` - id of the employee running a subcommand
+///* `command` - the optional subcommand to run
+///
+#[derive(Debug, Parser)]
+#[command(
+    name = "final_project",
+    about = "SNHU Financial - Client Management System",
+    long_about = "SNHU Financial - Client Management System\n\n\
+        Run without a subcommand to start the interactive menu."
+)]
+pub struct Cli {
+    /// Employee ID used to authenticate subcommands
+    #[arg(long, env = "CMS_EMPLOYEE_ID", global = true)]
+    pub employee_id: Option<i32>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// represents the available subcommands
+///
+///# Variants
+///
+///* `Migrate` - apply pending schema migrations, then exit
+///* `Clients` - client list operations
+///* `Client` - single client operations
+///* `Employee` - employee operations
+///* `Import` - import a client book CSV file
+///* `Export` - export a client book as CSV or JSON
+///
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Apply pending schema migrations, then exit
+    Migrate,
+    /// Client list operations
+    #[command(subcommand)]
+    Clients(ClientsCommand),
+    /// Single client operations
+    #[command(subcommand)]
+    Client(ClientCommand),
+    /// Employee operations
+    #[command(subcommand)]
+    Employee(EmployeeCommand),
+    /// Import clients from a CSV file in the clients_list.csv format
+    Import {
+        /// Path of the CSV file to import
+        path: PathBuf,
+    },
+    /// Export a client list as CSV or JSON
+    Export(ExportArgs),
+}
+
+/// client list subcommands
+#[derive(Debug, Subcommand)]
+pub enum ClientsCommand {
+    /// List clients, for one employee or the whole firm
+    List {
+        /// Only list the clients assigned to this employee ID
+        #[arg(long)]
+        employee: Option<i32>,
+        /// Only list the clients with this service: brokerage (1) or retirement (2)
+        #[arg(long, value_parser = parse_service)]
+        service: Option<ClientServiceChoice>,
+        /// Only list the clients with this name, case & spacing insensitive
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+/// single client subcommands
+#[derive(Debug, Subcommand)]
+pub enum ClientCommand {
+    /// Change a client's service choice
+    SetService {
+        /// ID of the client to change
+        client_id: i32,
+        /// New service: brokerage (1) or retirement (2)
+        #[arg(value_parser = parse_service)]
+        service: ClientServiceChoice,
+    },
+    /// Pair a client with a different employee
+    Reassign {
+        /// ID of the client to reassign
+        client_id: i32,
+        /// ID of the employee to pair the client with
+        #[arg(long)]
+        to: i32,
+    },
+}
+
+/// employee subcommands
+#[derive(Debug, Subcommand)]
+pub enum EmployeeCommand {
+    /// Add a new employee
+    Add {
+        /// Full name of the new employee
+        #[arg(long)]
+        name: String,
+        /// Role of the new employee: advisor, supervisor or admin
+        #[arg(long, default_value = "advisor", value_parser = parse_role)]
+        role: EmployeeRole,
+    },
+    /// Clear an employee's failed logins, ending any lockout
+    Unlock {
+        /// ID of the employee to unlock
+        employee_id: i32,
+    },
+}
+
+/// arguments of the export subcommand
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Path of the file to write
+    pub path: PathBuf,
+    /// Only export the clients assigned to this employee ID
+    #[arg(long)]
+    pub employee: Option<i32>,
+    /// Export format: csv or json
+    #[arg(long, default_value = "csv", value_parser = parse_format)]
+    pub format: ExportFormat,
+}
+
+/// clap value parser for client service choices
+fn parse_service(value: &str) -> Result<ClientServiceChoice, String> {
+    match ClientServiceChoice::from_name(value) {
+        Some(ClientServiceChoice::ReturnMenu) | None => Err(format!(
+            "'{}' is not a valid service, use brokerage (1) or retirement (2)",
+            value
+        )),
+        Some(service) => Ok(service),
+    }
+}
+
+/// clap value parser for employee roles
+fn parse_role(value: &str) -> Result<EmployeeRole, String> {
+    EmployeeRole::from_name(value).ok_or_else(|| {
+        format!(
+            "'{}' is not a valid role, use advisor (1), supervisor (2) or admin (3)",
+            value
+        )
+    })
+}
+
+/// clap value parser for export formats
+fn parse_format(value: &str) -> Result<ExportFormat, String> {
+    ExportFormat::from_name(value)
+        .ok_or_else(|| format!("'{}' is not a valid format, use csv or json", value))
+}
+
+/// Runs a single subcommand
+///
+/// authenticates the provided credentials, then runs the subcommand
+/// through the ClientHandler / EmployeeHandler, permission checked
+/// against the authenticated employee's role. The Migrate subcommand
+/// is handled by main before this function is called. Until an admin
+/// exists, `employee add --role admin` runs without credentials.
+///
+///# Arguments
+///
+///* 'cli: &Cli' - the parsed command line, including credentials
+///* 'command: &Command' - the subcommand to run
+///* 'database: Box<dyn DatabaseManager>' - boxed trait obj that implements DatabaseManager
+///
+///# Returns
+///
+///* 'Result<(), ApplicationError>' -
+///  * on success:
+///    * Ok(()) - the subcommand completed
+///  * on fail:
+///    * ApplicationError::AuthenticationError - missing or invalid credentials
+///    * ApplicationError::PermissionError - the employee's role does not permit the subcommand
+///    * ApplicationError - the error returned by the subcommand's operation
+///
+pub fn run_command(
+    cli: &Cli,
+    command: &Command,
+    database: Box<dyn DatabaseManager>,
+) -> Result<(), ApplicationError> {
+    let mut employee_handler = EmployeeHandler::new(database.clone_box())?;
+
+    // the first admin is added without credentials, no one can sign in before it exists
+    if let Command::Employee(EmployeeCommand::Add {
+        ref name,
+        role: EmployeeRole::Admin,
+    }) = *command
+    {
+        if !employee_handler.has_admin()? {
+            let new_password = new_employee_password()?;
+            let employee = Employee::new(0, name, &new_password, EmployeeRole::Admin)?;
+            let employee_id = employee_handler.bootstrap_admin(&employee)?;
+            println!("Added the first admin, Employee: {} {}", employee_id, name);
+            return Ok(());
+        }
+    }
+    let session = authenticate(cli, &mut employee_handler)?;
+
+    match *command {
+        Command::Migrate => Ok(()),
+        Command::Clients(ClientsCommand::List {
+            employee,
+            ref service,
+            ref name,
+        }) => {
+            let client_handler = ClientHandler::new(database)?;
+            let clients = if service.is_none() && name.is_none() {
+                collect_client_book(&client_handler, employee)?
+            } else {
+                // searched with the client cache indexes, in client id order
+                client_handler.find_clients(&ClientFilter {
+                    employee_id: employee,
+                    service: service.clone().map(|service| service as i32),
+                    name: name.clone(),
+                })
+            };
+            println!("client_id\tclient_name\tclient_service\tassigned_employee");
+            for client in &clients {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    client.get_client_id(),
+                    client.get_client_name(),
+                    client.get_client_service(),
+                    client.get_asn_employee()
+                );
+            }
+            Ok(())
+        }
+        Command::Client(ClientCommand::SetService {
+            client_id,
+            ref service,
+        }) => {
+            let mut client_handler = ClientHandler::new(database)?;
+            let mut updated_client = client_handler.get_client(client_id)?.clone();
+            updated_client.change_client_service(service.clone() as i32);
+            client_handler.update_client(&session, &updated_client)?;
+            println!("Client: {} now has service {:?}", client_id, service);
+            Ok(())
+        }
+        Command::Client(ClientCommand::Reassign { client_id, to }) => {
+            if !employee_handler.is_valid_employee_id(to)? {
+                return Err(ApplicationError::NoMatchError(format!(
+                    "No employee found with the ID: {}",
+                    to
+                )));
+            }
+            let mut client_handler = ClientHandler::new(database)?;
+            let mut updated_client = client_handler.get_client(client_id)?.clone();
+            updated_client.change_client_employee_pair(to);
+            client_handler.update_client(&session, &updated_client)?;
+            println!("Client: {} is now paired with Employee: {}", client_id, to);
+            Ok(())
+        }
+        Command::Employee(EmployeeCommand::Add { ref name, role }) => {
+            EmployeeHandler::authorize_admin(&session)?;
+            let new_password = new_employee_password()?;
+            let employee = Employee::new(0, name, &new_password, role)?;
+            let employee_id = employee_handler.add_new_employee(&session, &employee)?;
+            println!("Added Employee: {} {}", employee_id, name);
+            Ok(())
+        }
+        Command::Employee(EmployeeCommand::Unlock { employee_id }) => {
+            match employee_handler.unlock_employee(&session, employee_id)? {
+                Some(attempts) => println!(
+                    "Employee: {} is unlocked, cleared {} failed login attempt(s)",
+                    employee_id, attempts.failed_attempts
+                ),
+                None => println!("Employee: {} has no failed login attempts", employee_id),
+            }
+            Ok(())
+        }
+        Command::Import { ref path } => {
+            let mut client_handler = ClientHandler::new(database)?;
+            let report = import_clients(path, &session, &mut client_handler, &mut employee_handler)?;
+            report.print();
+            match report.failure {
+                Some(failure) => Err(ApplicationError::InputError(failure)),
+                None => Ok(()),
+            }
+        }
+        Command::Export(ref args) => {
+            let client_handler = ClientHandler::new(database)?;
+            let exported = export_clients(&client_handler, args.employee, args.format, &args.path)?;
+            println!("Exported {} client(s) to {}", exported, args.path.display());
+            Ok(())
+        }
+    }
+}
+
+/// Authenticates the employee running a subcommand
+///
+/// the employee ID comes from the command line, the password from
+/// CMS_PASSWORD, or from a prompt on stdin when it is not set.
+///
+///# Arguments
+///
+///* 'cli: &Cli' - the parsed command line
+///* 'employee_handler: &mut EmployeeHandler' - handler used to retrieve the stored hash
+///
+///# Returns
+///
+///* 'Result<Session, ApplicationError>' - the authenticated employee's session when
+///  the credentials are valid, otherwise ApplicationError::AuthenticationError
+///
+fn authenticate(
+    cli: &Cli,
+    employee_handler: &mut EmployeeHandler,
+) -> Result<Session, ApplicationError> {
+    let employee_id = cli.employee_id.ok_or_else(|| {
+        ApplicationError::AuthenticationError(
+            "Subcommands require --employee-id (or CMS_EMPLOYEE_ID)".to_string(),
+        )
+    })?;
+    let password = match env_password("CMS_PASSWORD") {
+        Some(password) => password,
+        None => get_password_input("Password")?,
+    };
+
+    let invalid = || {
+        ApplicationError::AuthenticationError("Invalid employee ID or password".to_string())
+    };
+    if !Authenticator::new().authenticate(employee_handler, employee_id, &password)? {
+        return Err(invalid());
+    }
+    employee_handler
+        .get_employee(employee_id)?
+        .map(|employee| Session::new(&employee))
+        .ok_or_else(invalid)
+}
+
+/// Gets the password of the employee being added
+///
+/// taken from CMS_NEW_EMPLOYEE_PASSWORD when it is set, otherwise
+/// prompted for twice on stdin, the same as the employee menu.
+///
+///# Returns
+///
+///* 'Result<String, ApplicationError>' - the new password, meeting the PasswordPolicy
+///
+///# Errors
+///
+///* 'ApplicationError::InputError' - the CMS_NEW_EMPLOYEE_PASSWORD password does not
+///  meet the PasswordPolicy
+///* 'ApplicationError::IoError' - failure to read the prompted password
+///
+fn new_employee_password() -> Result<String, ApplicationError> {
+    match env_password("CMS_NEW_EMPLOYEE_PASSWORD") {
+        Some(password) => {
+            PasswordPolicy::current().validate(&password)?;
+            Ok(password)
+        }
+        None => prompt_new_password(),
+    }
+}
+
+// reads a password from an environment variable, None when it is unset or empty.
+// trimmed the same as a prompted password
+fn env_password(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|password| password.trim().to_string())
+        .filter(|password| !password.is_empty())
+}
@@ -0,0 +1,483 @@
+// client_books.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module handles the import and export of client books as
+//! spreadsheet (CSV) files, and the export of them as JSON. Files use
+//! the same format as clients_list.csv: a client_name, client_service,
+//! assigned_employee header row, then one client per row. Exported CSV
+//! files can be imported again unchanged.
+
+// imports the File struct from the standard library fs module
+use std::fs::File;
+// imports the Write trait from the standard library io module
+use std::io::Write;
+// imports the Path struct from the standard library path module
+use std::path::Path;
+
+// imports the Serialize derive from the serde crate
+use serde::Serialize;
+
+// imports necessary errors from errors module
+use crate::errors::ApplicationError;
+// imports the Session of the signed in employee from the auth module
+use crate::auth::Session;
+// imports the Client struct from the firm_models module
+use crate::firm_models::Client;
+// imports the service choices from the menu module
+use crate::menu::ClientServiceChoice;
+// imports the client & employee handlers from the operation_handlers module
+use crate::operation_handlers::{ClientHandler, EmployeeHandler};
+
+//
+// ********************************************
+// client_books.rs module definitions begin here:
+// ********************************************
+//
+
+/// the header row of a client book CSV file, in column order
+pub const CLIENT_BOOK_HEADERS: [&str; 3] = ["client_name", "client_service", "assigned_employee"];
+
+/// represents the outcome of importing a single CSV row
+///
+///# Variants
+///
+///* `Accepted(Client)` - the row was valid, and produced this client
+///* `Rejected(String)` - the row was invalid, for the given reason
+///
+#[derive(Debug)]
+pub enum ImportOutcome {
+    Accepted(Client),
+    Rejected(String),
+}
+
+/// represents the import report for a client book
+///
+///# Fields
+///
+///* `rows: Vec<(u64, ImportOutcome)>` - the line number & outcome of each data row
+///* `failure: Option<String>` - set when the accepted rows could not be saved,
+///  the whole import was then rolled back
+///
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub rows: Vec<(u64, ImportOutcome)>,
+    pub failure: Option<String>,
+}
+
+impl ImportReport {
+    /// returns the clients from every accepted row
+    pub fn accepted(&self) -> Vec<&Client> {
+        self.rows
+            .iter()
+            .filter_map(|(_, outcome)| match outcome {
+                ImportOutcome::Accepted(client) => Some(client),
+                ImportOutcome::Rejected(_) => None,
+            })
+            .collect()
+    }
+
+    /// returns the number of rejected rows
+    pub fn rejected_count(&self) -> usize {
+        self.rows.len() - self.accepted().len()
+    }
+
+    /// console output function for the per-row import report
+    ///
+    /// outputs each row's outcome, followed by a summary line. When the
+    /// import was rolled back, the summary states that no clients were added.
+    ///
+    pub fn print(&self) {
+        println!("\nImport report:");
+        for (line, outcome) in &self.rows {
+            match outcome {
+                ImportOutcome::Accepted(client) => println!(
+                    "Row {}: accepted - {} (service {}, employee {})",
+                    line,
+                    client.get_client_name(),
+                    client.get_client_service(),
+                    client.get_asn_employee()
+                ),
+                ImportOutcome::Rejected(reason) => println!("Row {}: rejected - {}", line, reason),
+            }
+        }
+        match self.failure {
+            Some(ref failure) => println!(
+                "\nImport failed and was rolled back, no clients were added: {}",
+                failure
+            ),
+            None => println!(
+                "\nImported {} client(s), rejected {} row(s).",
+                self.accepted().len(),
+                self.rejected_count()
+            ),
+        }
+    }
+}
+
+/// Imports a client book CSV file
+///
+/// parses & validates every row of the file, then inserts the accepted
+/// rows through ClientHandler::new_client, all within one transaction.
+/// Invalid rows are rejected and reported, they do not stop the import.
+///
+///# Arguments
+///
+///* 'path: &Path' - path of the CSV file to import
+///* 'session: &Session' - the signed in employee the clients are added by. Rows
+///  an advisor may not add fail the import, which is then rolled back
+///* 'client_handler: &mut ClientHandler' - handler the clients are added through
+///* 'employee_handler: &mut EmployeeHandler' - handler used to validate employee ids
+///
+///# Returns
+///
+///* 'Result<ImportReport, ApplicationError>' -
+///  * on success:
+///    * Ok(ImportReport) - the outcome of every row. If saving the accepted
+///      rows failed, ImportReport.failure holds the reason.
+///  * on fail:
+///    * ApplicationError - the file could not be read, or its header row
+///      does not match CLIENT_BOOK_HEADERS
+///
+pub fn import_clients(
+    path: &Path,
+    session: &Session,
+    client_handler: &mut ClientHandler,
+    employee_handler: &mut EmployeeHandler,
+) -> Result<ImportReport, ApplicationError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+
+    let headers = reader.headers()?.clone();
+    if headers.iter().ne(CLIENT_BOOK_HEADERS.iter().cloned()) {
+        return Err(ApplicationError::InputError(format!(
+            "Expected the header row: {}",
+            CLIENT_BOOK_HEADERS.join(",")
+        )));
+    }
+
+    let mut report = ImportReport::default();
+    for record in reader.records() {
+        let (line, outcome) = match record {
+            Ok(record) => (
+                record.position().map_or(0, |position| position.line()),
+                validate_row(&record, employee_handler)?,
+            ),
+            Err(e) => (
+                e.position().map_or(0, |position| position.line()),
+                ImportOutcome::Rejected(e.to_string()),
+            ),
+        };
+        report.rows.push((line, outcome));
+    }
+
+    let accepted: Vec<Client> = report.accepted().into_iter().cloned().collect();
+    if !accepted.is_empty() {
+        let result = client_handler.batch(|handler| {
+            for client in &accepted {
+                handler.new_client(session, client)?;
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            report.failure = Some(e.to_string());
+        }
+    }
+    Ok(report)
+}
+
+/// Validates a single client book row
+///
+///# Arguments
+///
+///* 'record: &csv::StringRecord' - the parsed CSV row
+///* 'employee_handler: &mut EmployeeHandler' - handler used to validate employee ids
+///
+///# Returns
+///
+///* 'Result<ImportOutcome, ApplicationError>' -
+///  * on success:
+///    * Ok(ImportOutcome::Accepted) - row is valid, with the new (unsaved) client
+///    * Ok(ImportOutcome::Rejected) - row is invalid, with the reason
+///  * on fail:
+///    * ApplicationError - failure while looking up the employee
+///
+fn validate_row(
+    record: &csv::StringRecord,
+    employee_handler: &mut EmployeeHandler,
+) -> Result<ImportOutcome, ApplicationError> {
+    let client_name = record.get(0).unwrap_or("");
+    if client_name.is_empty() {
+        return Ok(ImportOutcome::Rejected("client_name is empty".to_string()));
+    }
+
+    let service_field = record.get(1).unwrap_or("");
+    let client_service = match service_field.parse::<i32>() {
+        Ok(service) => service,
+        Err(_) => {
+            return Ok(ImportOutcome::Rejected(format!(
+                "client_service '{}' is not an integer",
+                service_field
+            )))
+        }
+    };
+    match ClientServiceChoice::convert_i32(client_service) {
+        Some(ClientServiceChoice::Brokerage) | Some(ClientServiceChoice::Retirement) => {}
+        _ => {
+            return Ok(ImportOutcome::Rejected(format!(
+                "client_service {} is not a valid service (1 = Brokerage, 2 = Retirement)",
+                client_service
+            )))
+        }
+    }
+
+    let employee_field = record.get(2).unwrap_or("");
+    let assigned_employee = match employee_field.parse::<i32>() {
+        Ok(employee_id) => employee_id,
+        Err(_) => {
+            return Ok(ImportOutcome::Rejected(format!(
+                "assigned_employee '{}' is not an integer",
+                employee_field
+            )))
+        }
+    };
+    if !employee_handler.is_valid_employee_id(assigned_employee)? {
+        return Ok(ImportOutcome::Rejected(format!(
+            "assigned_employee {} is not a known employee ID",
+            assigned_employee
+        )));
+    }
+
+    Ok(ImportOutcome::Accepted(Client::new(
+        0, // assigned by the database on insert
+        client_name.to_string(),
+        client_service,
+        assigned_employee,
+    )))
+}
+
+/// represents a single exported client book row
+///
+/// field names match the CLIENT_BOOK_HEADERS columns, so JSON exports
+/// use the same names as the CSV files.
+///
+///# Fields
+///
+///* `client_name` - the client's name
+///* `client_service` - i32 client_service integer value
+///* `assigned_employee` - i32 id of the employee assigned to the client
+///
+#[derive(Debug, Serialize)]
+pub struct ClientBookRow<'a> {
+    pub client_name: &'a str,
+    pub client_service: i32,
+    pub assigned_employee: i32,
+}
+
+/// The format a client book is exported in
+///
+///# Variants
+///
+///* `Csv` - clients_list.csv compatible CSV
+///* `Json` - JSON array of ClientBookRow objects
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Converts a format name ("csv" / "json") to an ExportFormat
+    ///
+    ///# Arguments
+    ///
+    ///* 'value: &str' - the format name, case insensitive
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(ExportFormat)' - for a known format name
+    ///* 'None' - no match to a known format
+    ///
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.to_ascii_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Collects the client book for one employee, or for the whole firm
+///
+/// client ids come from ClientHandler::get_clients_for_employee, and
+/// each client is looked up in the handler's local AVLTree<Client>.
+/// Rows are ordered by assigned employee, then by client id.
+///
+///# Arguments
+///
+///* 'client_handler: &ClientHandler' - handler holding the local client structures
+///* 'employee_id: Option<i32>' - Some(id) for one employee's book, None for the firm
+///
+///# Returns
+///
+///* 'Result<Vec<&Client>, ApplicationError>' -
+///  * on success:
+///    * Ok(Vec<&Client>) - the clients in the book, empty when there are none
+///  * on fail:
+///    * ApplicationError - a paired client id was missing from the AVLTree
+///
+pub fn collect_client_book(
+    client_handler: &ClientHandler,
+    employee_id: Option<i32>,
+) -> Result<Vec<&Client>, ApplicationError> {
+    let employee_ids = match employee_id {
+        Some(employee_id) => vec![employee_id],
+        None => client_handler.get_paired_employee_ids(),
+    };
+
+    let mut clients = Vec::new();
+    for employee_id in employee_ids {
+        let client_ids = match client_handler.get_clients_for_employee(employee_id) {
+            Some(client_ids) => client_ids,
+            None => continue,
+        };
+        for &client_id in client_ids {
+            clients.push(client_handler.get_client(client_id)?);
+        }
+    }
+    Ok(clients)
+}
+
+/// Writes a client book in the requested format
+///
+///# Arguments
+///
+///* 'clients: &[&Client]' - the clients to write, in output order
+///* 'format: ExportFormat' - CSV or JSON
+///* 'writer: W' - destination the book is written to
+///
+///# Returns
+///
+///* 'Result<(), ApplicationError>' - Ok, or the error from writing / serializing
+///
+pub fn write_client_book<W: Write>(
+    clients: &[&Client],
+    format: ExportFormat,
+    mut writer: W,
+) -> Result<(), ApplicationError> {
+    let rows: Vec<ClientBookRow> = clients
+        .iter()
+        .map(|client| ClientBookRow {
+            client_name: client.get_client_name(),
+            client_service: client.get_client_service(),
+            assigned_employee: client.get_asn_employee(),
+        })
+        .collect();
+
+    match format {
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            csv_writer.write_record(CLIENT_BOOK_HEADERS.iter())?;
+            for row in &rows {
+                csv_writer.write_record(&[
+                    row.client_name.to_string(),
+                    row.client_service.to_string(),
+                    row.assigned_employee.to_string(),
+                ])?;
+            }
+            csv_writer.flush()?;
+        }
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &rows)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+/// Exports a client book to a file
+///
+/// collects the book for one employee, or for the whole firm, and
+/// writes it to the file at the provided path, replacing any existing file.
+///
+///# Arguments
+///
+///* 'client_handler: &ClientHandler' - handler holding the local client structures
+///* 'employee_id: Option<i32>' - Some(id) for one employee's book, None for the firm
+///* 'format: ExportFormat' - CSV or JSON
+///* 'path: &Path' - path of the file to write
+///
+///# Returns
+///
+///* 'Result<usize, ApplicationError>' -
+///  * on success:
+///    * Ok(usize) - the number of clients written
+///  * on fail:
+///    * ApplicationError - the book could not be collected, or the file written
+///
+pub fn export_clients(
+    client_handler: &ClientHandler,
+    employee_id: Option<i32>,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<usize, ApplicationError> {
+    let clients = collect_client_book(client_handler, employee_id)?;
+    write_client_book(&clients, format, File::create(path)?)?;
+    Ok(clients.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{DatabaseManager, InMemoryDatabase};
+    use crate::firm_models::{Employee, EmployeeRole};
+
+    #[test]
+    fn imported_clients_get_their_persisted_ids() {
+        let mut db: Box<dyn DatabaseManager> = Box::new(InMemoryDatabase::new());
+        let employee = Employee::from_stored(0, "Alan", "hash", EmployeeRole::Admin);
+        let employee_id = db.new_employee(&employee).unwrap();
+        let session = Session::new(&Employee::from_stored(
+            employee_id,
+            "Alan",
+            "hash",
+            EmployeeRole::Admin,
+        ));
+
+        let path = std::env::temp_dir().join(format!("client_book_{}.csv", std::process::id()));
+        std::fs::write(
+            &path,
+            format!(
+                "client_name,client_service,assigned_employee\n\
+                Ada,1,{0}\nGrace,2,{0}\n,1,{0}\n",
+                employee_id
+            ),
+        )
+        .unwrap();
+        let mut client_handler = ClientHandler::new(db.clone_box()).unwrap();
+        let mut employee_handler = EmployeeHandler::new(db.clone_box()).unwrap();
+        let report =
+            import_clients(&path, &session, &mut client_handler, &mut employee_handler).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(report.failure.is_none());
+        assert_eq!(report.rejected_count(), 1);
+        // the cached clients hold the ids the database assigned, not the 0 placeholder
+        let mut ids: Vec<i32> = db
+            .get_clients()
+            .unwrap()
+            .iter()
+            .map(|client| client.get_client_id())
+            .collect();
+        ids.sort();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&0));
+        for id in ids {
+            assert!(client_handler.get_client(id).is_ok());
+        }
+    }
+}
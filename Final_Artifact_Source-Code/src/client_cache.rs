@@ -0,0 +1,662 @@
+// client_cache.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module defines the local client cache used by the ClientHandler:
+//! the AVLTree<Client> ordered by client id, and secondary indexes of the
+//! clients by assigned employee, by service and by normalized name. Every
+//! insert, update and remove goes through the cache, which keeps the
+//! tree and the indexes in sync.
+
+// imports the ordered set struct from the standard library collections module
+use std::collections::BTreeSet;
+// imports the range bound enum from the standard library ops module
+use std::ops::Bound;
+
+// imports the AVL tree & index structures from the data_structs module
+use crate::data_structs::{AVLTree, SecondaryIndex};
+// imports necessary errors from errors module
+use crate::errors::ApplicationError;
+// imports the Client struct from the firm_models module
+use crate::firm_models::Client;
+
+//
+// ********************************************
+// client_cache.rs module definitions begin here:
+// ********************************************
+//
+
+/// Converts a client name to the form used by the name index
+///
+/// surrounding whitespace is removed, inner whitespace is collapsed to
+/// single spaces, and letters are lowercased, so "  Jane   DOE" and
+/// "jane doe" are indexed together.
+///
+///# Arguments
+///
+///* 'name: &str' - the client name, as entered or stored
+///
+///# Returns
+///
+///* 'String' - the normalized name
+///
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// the most name matches returned by a name search
+pub const NAME_SEARCH_LIMIT: usize = 10;
+
+/// represents how closely a client name matched a name search
+///
+/// variants are ordered from the best match to the worst, so sorting by
+/// NameMatch ranks the results.
+///
+///# Variants
+///
+///* `Exact` - the normalized names are equal
+///* `Prefix` - the name, or one of its words, starts with the search
+///* `Contains` - the search appears within the name
+///* `Similar(usize)` - within the typo tolerance, holds the edit distance
+///  to the closest of the name, one of its words, or its start
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NameMatch {
+    Exact,
+    Prefix,
+    Contains,
+    Similar(usize),
+}
+
+impl NameMatch {
+    /// returns the display name of the match
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Exact => "exact",
+            Self::Prefix => "prefix",
+            Self::Contains => "contains",
+            Self::Similar(_) => "similar",
+        }
+    }
+}
+
+/// represents a single result of a name search
+///
+///# Fields
+///
+///* `client` - the matching cached client
+///* `name_match` - how closely the client's name matched
+///
+#[derive(Clone, Debug)]
+pub struct ClientMatch<'a> {
+    pub client: &'a Client,
+    pub name_match: NameMatch,
+}
+
+/// Calculates the edit (Levenshtein) distance between two strings
+///
+/// the fewest single character insertions, deletions or substitutions
+/// that change one string into the other. Compares characters, not bytes.
+///
+///# Arguments
+///
+///* 'from: &str' / 'to: &str' - the strings to compare
+///
+///# Returns
+///
+///* 'usize' - the edit distance, 0 when the strings are equal
+///
+///# Behavior
+///
+///* 1. keeps one row of the distance table, the distances from the start of
+///     from to every prefix of to
+///* 2. for each character of from, the next row is calculated from the
+///     previous one: the cheapest of a deletion, an insertion or a substitution
+///* 3. the last value of the last row is the distance between the whole strings
+///
+pub fn edit_distance(from: &str, to: &str) -> usize {
+    let to: Vec<char> = to.chars().collect();
+    let mut row: Vec<usize> = (0..=to.len()).collect();
+    for (i, from_char) in from.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &to_char) in to.iter().enumerate() {
+            let substitution = diagonal + usize::from(from_char != to_char);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[to.len()]
+}
+
+/// Compares a normalized name search against a normalized client name
+///
+///# Arguments
+///
+///* 'search: &str' - the normalized search
+///* 'name: &str' - the normalized client name
+///
+///# Returns
+///
+///* 'Option<NameMatch>' - how closely the name matched, None when it did not.
+///  Searches shorter than 4 characters must match exactly, longer ones
+///  tolerate one typo per 3 characters.
+///
+fn match_name(search: &str, name: &str) -> Option<NameMatch> {
+    if name == search {
+        return Some(NameMatch::Exact);
+    }
+    if name.starts_with(search) || name.split(' ').any(|word| word.starts_with(search)) {
+        return Some(NameMatch::Prefix);
+    }
+    if name.contains(search) {
+        return Some(NameMatch::Contains);
+    }
+
+    let search_length = search.chars().count();
+    let name_start: String = name.chars().take(search_length).collect();
+    let distance = name
+        .split(' ')
+        .chain(vec![name, name_start.as_str()])
+        .map(|candidate| edit_distance(search, candidate))
+        .min()
+        .unwrap_or(usize::MAX);
+    // a typo in 1 to 3 characters matches too many unrelated names
+    let tolerance = if search_length < 4 {
+        0
+    } else {
+        search_length / 3
+    };
+    if distance <= tolerance {
+        Some(NameMatch::Similar(distance))
+    } else {
+        None
+    }
+}
+
+/// represents a search of the cached clients
+///
+/// every field is optional, None matches any client.
+///
+///# Fields
+///
+///* `employee_id` - only clients assigned to this employee
+///* `service` - only clients with this client_service value
+///* `name` - only clients with this name, compared after normalize_name
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ClientFilter {
+    pub employee_id: Option<i32>,
+    pub service: Option<i32>,
+    pub name: Option<String>,
+}
+
+/// Represents the local client cache
+///
+///# Fields
+///
+///* `clients: AVLTree<Client>` - every client, ordered by client_id
+///* `by_employee: SecondaryIndex<Client, i32>` - client ids by assigned employee id
+///* `by_service: SecondaryIndex<Client, i32>` - client ids by client_service value
+///* `by_name: SecondaryIndex<Client, String>` - client ids by normalized client name
+///
+pub struct ClientCache {
+    clients: AVLTree<Client>,
+    by_employee: SecondaryIndex<Client, i32>,
+    by_service: SecondaryIndex<Client, i32>,
+    by_name: SecondaryIndex<Client, String>,
+}
+
+impl Default for ClientCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClientCache {
+    /// Creates a new, empty client cache
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the empty ClientCache
+    ///
+    pub fn new() -> Self {
+        ClientCache {
+            clients: AVLTree::new(),
+            by_employee: SecondaryIndex::new(Client::get_asn_employee),
+            by_service: SecondaryIndex::new(Client::get_client_service),
+            by_name: SecondaryIndex::new(|client: &Client| {
+                normalize_name(client.get_client_name())
+            }),
+        }
+    }
+
+    /// removes every client from the tree and the indexes
+    pub fn clear(&mut self) {
+        self.clients.clear();
+        self.by_employee.clear();
+        self.by_service.clear();
+        self.by_name.clear();
+    }
+
+    /// Adds a new client to the tree and the indexes
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (ClientCache)
+    ///* 'client: Client' - the client to add
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(()) - the client was added
+    ///  * on fail:
+    ///    * ApplicationError::InputError - a client with the id is already cached,
+    ///      nothing was changed
+    ///
+    pub fn insert(&mut self, client: Client) -> Result<(), ApplicationError> {
+        self.clients.insert(client.clone())?;
+        self.index(&client);
+        Ok(())
+    }
+
+    /// Replaces a cached client with its updated values
+    ///
+    /// the client is updated in place in the tree, and moved between
+    /// index entries when its employee, service or name changed.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (ClientCache)
+    ///* 'client: Client' - the updated client, with the id of the cached one
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Client, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Client) - the client as it was before the update
+    ///  * on fail:
+    ///    * ApplicationError::NoMatchError - no client with the id is cached,
+    ///      nothing was changed
+    ///
+    pub fn update(&mut self, client: Client) -> Result<Client, ApplicationError> {
+        let old_client = self.clients.replace(client.clone())?;
+        self.unindex(&old_client);
+        self.index(&client);
+        Ok(old_client)
+    }
+
+    /// Removes a client from the tree and the indexes
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (ClientCache)
+    ///* 'client_id: i32' - id of the client to remove
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Client, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Client) - the removed client
+    ///  * on fail:
+    ///    * ApplicationError::NoMatchError - no client with the id is cached
+    ///
+    pub fn remove(&mut self, client_id: i32) -> Result<Client, ApplicationError> {
+        let client = self.clients.remove(&client_id)?;
+        self.unindex(&client);
+        Ok(client)
+    }
+
+    /// adds a client's id to every index
+    fn index(&mut self, client: &Client) {
+        self.by_employee.insert(client);
+        self.by_service.insert(client);
+        self.by_name.insert(client);
+    }
+
+    /// removes a client's id from every index, using its cached values
+    fn unindex(&mut self, client: &Client) {
+        self.by_employee.remove(client);
+        self.by_service.remove(client);
+        self.by_name.remove(client);
+    }
+
+    /// returns the cached client with the id, or NoMatchError
+    pub fn get(&self, client_id: i32) -> Result<&Client, ApplicationError> {
+        self.clients.find(&client_id).map_err(|_| {
+            ApplicationError::NoMatchError(format!(
+                "No client match found for the provided ID: {}",
+                client_id
+            ))
+        })
+    }
+
+    /// returns the number of cached clients
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    /// returns true when no clients are cached
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    /// Returns a page of every cached client, in client id order
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (ClientCache)
+    ///* 'after: Option<i32>' - the last client id of the previous page, None for the first page
+    ///* 'limit: usize' - the maximum number of clients on the page
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<&Client>' - the clients of the page, empty when there are no more clients
+    ///
+    pub fn page(&self, after: Option<i32>, limit: usize) -> Vec<&Client> {
+        let start = after.map_or(Bound::Unbounded, Bound::Excluded);
+        self.clients
+            .range((start, Bound::Unbounded))
+            .take(limit)
+            .collect()
+    }
+
+    /// returns the sorted ids of the clients assigned to the employee, None when there are none
+    pub fn clients_for_employee(&self, employee_id: i32) -> Option<&BTreeSet<i32>> {
+        self.by_employee.get(&employee_id)
+    }
+
+    /// returns the sorted ids of the clients with the service, None when there are none
+    pub fn clients_with_service(&self, service: i32) -> Option<&BTreeSet<i32>> {
+        self.by_service.get(&service)
+    }
+
+    /// returns the sorted ids of the clients with the name, compared after normalize_name
+    pub fn clients_named(&self, name: &str) -> Option<&BTreeSet<i32>> {
+        self.by_name.get(&normalize_name(name))
+    }
+
+    /// Searches the cached clients by name
+    ///
+    /// compares the search against every name in the name index, so that
+    /// clients sharing a name are compared once. Matching is case & spacing
+    /// insensitive, accepts the start of the name or of one of its words,
+    /// and tolerates typos.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (ClientCache)
+    ///* 'search: &str' - the name, or part of it, as entered
+    ///* 'limit: usize' - the maximum number of matches returned
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<ClientMatch>' - the matches, best first, ties in client id order.
+    ///  Empty when the search is blank or nothing matched.
+    ///
+    pub fn search_by_name(&self, search: &str, limit: usize) -> Vec<ClientMatch<'_>> {
+        let search = normalize_name(search);
+        if search.is_empty() || self.is_empty() {
+            return Vec::new();
+        }
+        let mut matches = Vec::new();
+        for (name, client_ids) in self.by_name.iter() {
+            if let Some(name_match) = match_name(&search, name) {
+                for client_id in client_ids {
+                    if let Ok(client) = self.clients.find(client_id) {
+                        matches.push(ClientMatch { client, name_match });
+                    }
+                }
+            }
+        }
+        matches.sort_by_key(|found| (found.name_match, found.client.get_client_id()));
+        matches.truncate(limit);
+        matches
+    }
+
+    /// returns the id of every employee with at least one client, in ascending order
+    pub fn employee_ids(&self) -> Vec<i32> {
+        self.by_employee.values().cloned().collect()
+    }
+
+    /// Searches the cached clients using the indexes
+    ///
+    /// e.g. every retirement client of employee 5. Each set field of the
+    /// filter selects the client ids of one index, the result is the
+    /// clients in all of them. An empty filter returns every client.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (ClientCache)
+    ///* 'filter: &ClientFilter' - the search
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<&Client>' - the matching clients in client id order, empty when none match
+    ///
+    ///# Behavior
+    ///
+    ///* 1. looks up the id set of each set filter field, an unmatched field
+    ///     means no client can match
+    ///* 2. starts from the smallest of those sets, keeping the ids found in every other set
+    ///* 3. looks up each remaining id in the AVLTree
+    ///
+    pub fn find(&self, filter: &ClientFilter) -> Vec<&Client> {
+        let mut id_sets = Vec::new();
+        if let Some(employee_id) = filter.employee_id {
+            id_sets.push(self.clients_for_employee(employee_id));
+        }
+        if let Some(service) = filter.service {
+            id_sets.push(self.clients_with_service(service));
+        }
+        if let Some(ref name) = filter.name {
+            id_sets.push(self.clients_named(name));
+        }
+        if id_sets.is_empty() {
+            return self.clients.iter().collect();
+        }
+
+        let mut id_sets: Vec<&BTreeSet<i32>> = match id_sets.into_iter().collect() {
+            Some(id_sets) => id_sets,
+            None => return Vec::new(),
+        };
+        id_sets.sort_by_key(|client_ids| client_ids.len());
+        id_sets[0]
+            .iter()
+            .filter(|client_id| {
+                id_sets[1..]
+                    .iter()
+                    .all(|client_ids| client_ids.contains(client_id))
+            })
+            .filter_map(|&client_id| self.clients.find(&client_id).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_of(clients: &[(i32, &str, i32, i32)]) -> ClientCache {
+        let mut cache = ClientCache::new();
+        for &(id, name, service, employee) in clients {
+            cache
+                .insert(Client::new(id, name.to_string(), service, employee))
+                .unwrap();
+        }
+        cache
+    }
+
+    fn ids(clients: Option<&BTreeSet<i32>>) -> Vec<i32> {
+        clients.map_or(Vec::new(), |client_ids| {
+            client_ids.iter().cloned().collect()
+        })
+    }
+
+    fn found_ids(clients: Vec<&Client>) -> Vec<i32> {
+        clients
+            .iter()
+            .map(|client| client.get_client_id())
+            .collect()
+    }
+
+    fn found(matches: &[ClientMatch]) -> Vec<(i32, NameMatch)> {
+        matches
+            .iter()
+            .map(|found| (found.client.get_client_id(), found.name_match))
+            .collect()
+    }
+
+    #[test]
+    fn edit_distance_counts_single_character_edits() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("anna", "anna"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("anna", "anne"), 1);
+        assert_eq!(edit_distance("smith", "smyth"), 1);
+        assert_eq!(edit_distance("jon", "john"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        // characters, not bytes
+        assert_eq!(edit_distance("zoë", "zoe"), 1);
+    }
+
+    #[test]
+    fn name_matches_are_ranked() {
+        let cache = cache_of(&[
+            (1, "Bob Stone", 1, 1),
+            (2, "Anne Fox", 1, 1),
+            (3, "Joanna Ray", 1, 1),
+            (4, "Annabel Lee", 1, 1),
+            (5, "ANNA", 1, 1),
+        ]);
+        let matches = cache.search_by_name("  anna ", NAME_SEARCH_LIMIT);
+        assert_eq!(
+            found(&matches),
+            vec![
+                (5, NameMatch::Exact),
+                (4, NameMatch::Prefix),
+                (3, NameMatch::Contains),
+                (2, NameMatch::Similar(1)),
+            ]
+        );
+        assert_eq!(cache.search_by_name("anna", 2).len(), 2);
+        assert!(cache.search_by_name("   ", NAME_SEARCH_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn short_searches_tolerate_no_typos() {
+        let cache = cache_of(&[
+            (1, "Zed Hill", 1, 1),
+            (2, "Liz Moore", 1, 1),
+            (3, "Amy Cole", 1, 1),
+            (4, "Rob Lane", 1, 1),
+        ]);
+        let matches = cache.search_by_name("z", NAME_SEARCH_LIMIT);
+        assert_eq!(
+            found(&matches),
+            vec![(1, NameMatch::Prefix), (2, NameMatch::Contains)]
+        );
+        assert!(cache.search_by_name("bob", NAME_SEARCH_LIMIT).is_empty());
+        // one typo from 4 characters
+        let matches = cache.search_by_name("lanr", NAME_SEARCH_LIMIT);
+        assert_eq!(found(&matches), vec![(4, NameMatch::Similar(1))]);
+    }
+
+    #[test]
+    fn indexes_follow_insert_update_and_remove() {
+        assert!(ClientCache::default().is_empty());
+        let mut cache = cache_of(&[(1, "Ann Lee", 1, 5), (2, "Bob Ray", 2, 5)]);
+        assert_eq!(cache.len(), 2);
+        assert!(!cache.is_empty());
+        assert_eq!(ids(cache.clients_for_employee(5)), vec![1, 2]);
+        assert_eq!(ids(cache.clients_with_service(1)), vec![1]);
+        assert_eq!(ids(cache.clients_named("ann  LEE")), vec![1]);
+        assert!(cache
+            .insert(Client::new(1, "Ann Lee".to_string(), 1, 5))
+            .is_err());
+
+        // employee change
+        let old = cache
+            .update(Client::new(1, "Ann Lee".to_string(), 1, 7))
+            .unwrap();
+        assert_eq!(old.get_asn_employee(), 5);
+        assert_eq!(ids(cache.clients_for_employee(5)), vec![2]);
+        assert_eq!(ids(cache.clients_for_employee(7)), vec![1]);
+        assert_eq!(cache.employee_ids(), vec![5, 7]);
+
+        // service change
+        cache
+            .update(Client::new(2, "Bob Ray".to_string(), 1, 5))
+            .unwrap();
+        assert_eq!(ids(cache.clients_with_service(1)), vec![1, 2]);
+        assert!(cache.clients_with_service(2).is_none());
+
+        // name change
+        cache
+            .update(Client::new(1, "Ann Fox".to_string(), 1, 7))
+            .unwrap();
+        assert!(cache.clients_named("ann lee").is_none());
+        assert_eq!(ids(cache.clients_named("Ann Fox")), vec![1]);
+        assert!(cache
+            .update(Client::new(9, "Nobody".to_string(), 1, 1))
+            .is_err());
+
+        cache.remove(1).unwrap();
+        assert!(cache.clients_for_employee(7).is_none());
+        assert!(cache.clients_named("ann fox").is_none());
+        assert_eq!(ids(cache.clients_with_service(1)), vec![2]);
+        assert!(cache.remove(1).is_err());
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+        assert!(cache.is_empty());
+        assert!(cache.employee_ids().is_empty());
+    }
+
+    #[test]
+    fn find_intersects_the_filter_fields() {
+        let cache = cache_of(&[
+            (1, "Ann Lee", 1, 5),
+            (2, "Ann Lee", 2, 5),
+            (3, "Bob Ray", 1, 5),
+            (4, "Ann Lee", 1, 6),
+        ]);
+        let filter = |employee_id, service, name: Option<&str>| ClientFilter {
+            employee_id,
+            service,
+            name: name.map(str::to_string),
+        };
+        assert_eq!(
+            found_ids(cache.find(&ClientFilter::default())),
+            vec![1, 2, 3, 4]
+        );
+        assert_eq!(
+            found_ids(cache.find(&filter(Some(5), None, None))),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            found_ids(cache.find(&filter(Some(5), Some(1), None))),
+            vec![1, 3]
+        );
+        assert_eq!(
+            found_ids(cache.find(&filter(None, Some(1), Some("ann lee")))),
+            vec![1, 4]
+        );
+        assert_eq!(
+            found_ids(cache.find(&filter(Some(5), Some(1), Some("ANN LEE")))),
+            vec![1]
+        );
+        // each field matches some client, but no client matches them all
+        assert!(cache.find(&filter(Some(6), Some(2), None)).is_empty());
+        // a field matching no client at all
+        assert!(cache.find(&filter(Some(9), None, None)).is_empty());
+        assert!(cache.find(&filter(None, None, Some("Nobody"))).is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+// client_history.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module defines the client history: every reassignment of a
+//! client to a different employee, and every change of a client's
+//! service, made through ClientHandler::update_client. Entries are
+//! written to the `client_history` table in the same transaction as
+//! the change, and can be searched by client, employee, action and
+//! date range, then exported as CSV.
+//! Uses the [chrono](https://docs.rs/chrono/latest/chrono/) crate for dates.
+
+// imports the File struct from the standard library fs module
+use std::fs::File;
+// imports the Path struct from the standard library path module
+use std::path::Path;
+
+// imports the date & time items from the chrono crate
+use chrono::{DateTime, Days, NaiveDate, Utc};
+
+// imports the Session of the signed in employee from the auth module
+use crate::auth::Session;
+// imports necessary errors from errors module
+use crate::errors::ApplicationError;
+
+//
+// ********************************************
+// client_history.rs module definitions begin here:
+// ********************************************
+//
+
+/// the header row of an exported client history CSV file, in column order
+pub const HISTORY_HEADERS: [&str; 6] = [
+    "occurred_at",
+    "client_id",
+    "changed_by",
+    "action",
+    "old_value",
+    "new_value",
+];
+
+/// the number of history entries displayed per page by the menu
+pub const HISTORY_PAGE_SIZE: usize = 20;
+
+/// represents the kind of change recorded by a history entry
+///
+/// stored as an integer in the `action` column of the client_history table.
+///
+///# Variants
+///
+///* `Reassign` - the client was paired with a different employee, the
+///  values are the old / new employee ids (1)
+///* `ServiceChange` - the client's service changed, the values are the
+///  old / new client_service values (2)
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryAction {
+    Reassign = 1,
+    ServiceChange = 2,
+}
+
+impl HistoryAction {
+    /// Converts a stored / input integer value to enum variant.
+    ///
+    ///# Arguments
+    ///
+    ///* 'value' - i32 integer value of the action
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(HistoryAction)' - for matching explicitly defined enum variant
+    ///* 'None' - returns None when no match to a defined enum variant
+    ///
+    pub fn convert_i32(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Reassign),
+            2 => Some(Self::ServiceChange),
+            _ => None,
+        }
+    }
+
+    /// returns the display name of the action
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Reassign => "reassign",
+            Self::ServiceChange => "service_change",
+        }
+    }
+}
+
+/// represents a single entry of the client history
+///
+///# Fields
+///
+///* `occurred_at` - i64, unix timestamp (seconds, UTC) of the change
+///* `client_id` - i32, id of the changed client
+///* `changed_by` - i32, id of the signed in employee who made the change
+///* `action` - HistoryAction, the kind of change
+///* `old_value` - i32, employee id / service before the change
+///* `new_value` - i32, employee id / service after the change
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClientHistoryEntry {
+    pub occurred_at: i64,
+    pub client_id: i32,
+    pub changed_by: i32,
+    pub action: HistoryAction,
+    pub old_value: i32,
+    pub new_value: i32,
+}
+
+impl ClientHistoryEntry {
+    /// Creates a new history entry, timestamped with the current time
+    ///
+    ///# Arguments
+    ///
+    ///* 'session' - the signed in employee who made the change
+    ///* 'client_id' - id of the changed client
+    ///* 'action' - the kind of change
+    ///* 'old_value' / 'new_value' - the employee id / service before and after
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the new ClientHistoryEntry
+    ///
+    pub fn new(
+        session: &Session,
+        client_id: i32,
+        action: HistoryAction,
+        old_value: i32,
+        new_value: i32,
+    ) -> Self {
+        ClientHistoryEntry {
+            occurred_at: Utc::now().timestamp(),
+            client_id,
+            changed_by: session.get_employee_id(),
+            action,
+            old_value,
+            new_value,
+        }
+    }
+}
+
+/// represents a search of the client history
+///
+/// every field is optional, None matches any entry.
+///
+///# Fields
+///
+///* `client_id` - only entries of this client
+///* `employee_id` - only entries made by this employee, or reassigning a
+///  client to / from them
+///* `action` - only entries of this kind
+///* `from` - only entries at or after this unix timestamp
+///* `until` - only entries before this unix timestamp
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryFilter {
+    pub client_id: Option<i32>,
+    pub employee_id: Option<i32>,
+    pub action: Option<HistoryAction>,
+    pub from: Option<i64>,
+    pub until: Option<i64>,
+}
+
+impl HistoryFilter {
+    /// returns true when the entry matches every set field of the filter
+    ///
+    /// used by backends without SQL, the SQL backends build the same
+    /// conditions into their query.
+    // map_or(true, ..) rather than is_none_or, which needs Rust 1.82
+    #[allow(clippy::unnecessary_map_or)]
+    pub fn matches(&self, entry: &ClientHistoryEntry) -> bool {
+        self.client_id.map_or(true, |id| entry.client_id == id)
+            && self.employee_id.map_or(true, |id| {
+                entry.changed_by == id
+                    || (entry.action == HistoryAction::Reassign
+                        && (entry.old_value == id || entry.new_value == id))
+            })
+            && self.action.map_or(true, |action| entry.action == action)
+            && self.from.map_or(true, |from| entry.occurred_at >= from)
+            && self.until.map_or(true, |until| entry.occurred_at < until)
+    }
+}
+
+/// Converts a YYYY-MM-DD date to the unix timestamp of its start (UTC)
+///
+///# Arguments
+///
+///* 'value: &str' - the date, e.g. 2024-07-11
+///* 'end_of_day: bool' - true to return the start of the following day
+///  instead, so that the date itself is included in a range
+///
+///# Returns
+///
+///* 'Result<i64, ApplicationError>' - the timestamp, or InputError for an invalid date
+///
+pub fn parse_date(value: &str, end_of_day: bool) -> Result<i64, ApplicationError> {
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").map_err(|e| {
+        ApplicationError::InputError(format!("'{}' is not a YYYY-MM-DD date: {}", value, e))
+    })?;
+    let date = if end_of_day {
+        date.checked_add_days(Days::new(1)).ok_or_else(|| {
+            ApplicationError::InputError(format!("'{}' is out of range", value))
+        })?
+    } else {
+        date
+    };
+    Ok(date.and_hms_opt(0, 0, 0).map_or(0, |start| start.and_utc().timestamp()))
+}
+
+/// formats a unix timestamp as YYYY-MM-DD HH:MM:SS (UTC)
+pub fn format_timestamp(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .map_or_else(|| timestamp.to_string(), |time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+}
+
+/// Exports client history entries to a CSV file
+///
+/// writes the HISTORY_HEADERS row, then one row per entry, replacing
+/// any existing file at the provided path.
+///
+///# Arguments
+///
+///* 'entries: &[ClientHistoryEntry]' - the entries to write, in output order
+///* 'path: &Path' - path of the file to write
+///
+///# Returns
+///
+///* 'Result<usize, ApplicationError>' -
+///  * on success:
+///    * Ok(usize) - the number of entries written
+///  * on fail:
+///    * ApplicationError - the file could not be written
+///
+pub fn export_history(
+    entries: &[ClientHistoryEntry],
+    path: &Path,
+) -> Result<usize, ApplicationError> {
+    let mut csv_writer = csv::Writer::from_writer(File::create(path)?);
+    csv_writer.write_record(HISTORY_HEADERS.iter())?;
+    for entry in entries {
+        csv_writer.write_record(&[
+            format_timestamp(entry.occurred_at),
+            entry.client_id.to_string(),
+            entry.changed_by.to_string(),
+            entry.action.name().to_string(),
+            entry.old_value.to_string(),
+            entry.new_value.to_string(),
+        ])?;
+    }
+    csv_writer.flush()?;
+    Ok(entries.len())
+}
@@ -13,6 +13,10 @@
 
 // imports the Box struct from the standard library boxed module
 use std::boxed::Box;
+// imports the ordered map & set structs from the standard library collections module
+use std::collections::{BTreeMap, BTreeSet};
+// imports the range bound types from the standard library ops module
+use std::ops::{Bound, RangeBounds};
 
 // imports all public items from the firm_models module
 use crate::{errors::ApplicationError, firm_models::*};
@@ -32,10 +36,30 @@ use crate::{errors::ApplicationError, firm_models::*};
 ///# Fields
 ///
 ///* `root: Option<Box<Node<T>>>` - optional Box containing Node<T>
+///* `size: usize` - the number of nodes / data objects in the tree
 ///
 pub struct AVLTree<T> {
     // optional box containing an implemented node<T> struct
     root: Option<Box<Node<T>>>,
+    // kept up to date by insert / remove, so len is O(1)
+    size: usize,
+}
+
+/// Represents a borrowing in order iterator over an AVLTree<T>
+///
+/// Yields references to the data objects from the smallest key to the
+/// largest, stopping after the upper bound of the range when one was
+/// given. Created by AVLTree::iter and AVLTree::range.
+///
+///# Fields
+///
+///* `stack: Vec<&Node<T>>` - nodes whose data & right subtree are still to be visited,
+///  the next node to yield is on top
+///* `end: Bound<T::Key>` - the upper bound of the keys yielded
+///
+pub struct Iter<'a, T: 'a + Identification> {
+    stack: Vec<&'a Node<T>>,
+    end: Bound<T::Key>,
 }
 
 /// Represents the node struct for the tree
@@ -67,7 +91,7 @@ pub struct Node<T> {
     height: i32,
 }
 
-impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
+impl<T: Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///AVL tree constructor function to create new, empty tree
     ///
     /// function creates an new, empty implemention of the AVL tree struct
@@ -78,7 +102,10 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///* 'Self' - this implemented struct object instance, AvlTree<T>
     ///
     pub fn new() -> Self {
-        AVLTree { root: None }
+        AVLTree {
+            root: None,
+            size: 0,
+        }
     }
     /// Checks the tree to see if it is empty
     ///
@@ -129,6 +156,158 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///
     pub fn clear(&mut self) {
         self.root = None;
+        self.size = 0;
+    }
+    /// Returns the number of data objects stored in the tree
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (this implemented AVLTree struct instance)
+    ///
+    ///# Returns
+    ///
+    ///* 'usize' - the number of nodes, 0 when the tree is empty
+    ///
+    pub fn len(&self) -> usize {
+        self.size
+    }
+    /// Creates an in order iterator over every data object in the tree
+    ///
+    /// borrows the tree, yielding the data objects from the smallest key
+    /// (client_id) to the largest. O(1) amortized per step, the iterator
+    /// holds at most the height of the tree in nodes.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (this implemented AVLTree struct instance)
+    ///
+    ///# Returns
+    ///
+    ///* 'Iter<T>' - the in order iterator
+    ///
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.range(..)
+    }
+    /// Creates an in order iterator over the data objects within a key range
+    ///
+    /// accepts any range of keys, e.g. `10..50`, `10..=50`, `..50` or `10..`.
+    /// Subtrees entirely below the start of the range are skipped, so
+    /// reaching the first match is O(log n).
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (this implemented AVLTree struct instance)
+    ///* 'range: R' - the range of keys (client_id values for clients) to yield
+    ///
+    ///# Returns
+    ///
+    ///* 'Iter<T>' - the in order iterator, yields nothing when no key is in range
+    ///
+    ///# Behavior
+    ///
+    ///* 1. starting at the root, a node within the lower bound is pushed on the
+    ///     stack and the search continues with its left child, the smaller keys
+    ///* 2. a node below the lower bound is skipped along with its left subtree,
+    ///     the search continues with its right child
+    ///* 3. the top of the stack is then the smallest key within the lower bound
+    ///
+    pub fn range<R: RangeBounds<T::Key>>(&self, range: R) -> Iter<'_, T> {
+        let mut stack = Vec::new();
+        let mut current = self.root.as_ref();
+        while let Some(node) = current {
+            let key = node.data.get_key();
+            let after_start = match range.start_bound() {
+                Bound::Included(start) => key >= start,
+                Bound::Excluded(start) => key > start,
+                Bound::Unbounded => true,
+            };
+            if after_start {
+                stack.push(&**node);
+                current = node.left.as_ref();
+            } else {
+                current = node.right.as_ref();
+            }
+        }
+        let end = match range.end_bound() {
+            Bound::Included(end) => Bound::Included(end.clone()),
+            Bound::Excluded(end) => Bound::Excluded(end.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Iter { stack, end }
+    }
+    /// Returns the data object with the smallest key, None when the tree is empty
+    pub fn min(&self) -> Option<&T> {
+        let mut current = self.root.as_ref()?;
+        while let Some(left) = current.left.as_ref() {
+            current = left;
+        }
+        Some(&current.data)
+    }
+    /// Returns the data object with the largest key, None when the tree is empty
+    pub fn max(&self) -> Option<&T> {
+        let mut current = self.root.as_ref()?;
+        while let Some(right) = current.right.as_ref() {
+            current = right;
+        }
+        Some(&current.data)
+    }
+    /// Finds the data object with the closest key smaller than the provided key
+    ///
+    /// the key itself does not need to exist in the tree.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (this implemented AVLTree struct instance)
+    ///* 'key: &T::Key' - the key value to search below
+    ///
+    ///# Returns
+    ///
+    ///* 'Option<&T>' -
+    ///  * if Some - the data object with the largest key less than key
+    ///  * if None - no key in the tree is less than key
+    ///
+    pub fn predecessor(&self, key: &T::Key) -> Option<&T> {
+        let mut current = self.root.as_ref();
+        let mut closest = None;
+        while let Some(node) = current {
+            if node.data.get_key() < key {
+                // a candidate, a closer one may be in the right subtree
+                closest = Some(&node.data);
+                current = node.right.as_ref();
+            } else {
+                current = node.left.as_ref();
+            }
+        }
+        closest
+    }
+    /// Finds the data object with the closest key larger than the provided key
+    ///
+    /// the key itself does not need to exist in the tree.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (this implemented AVLTree struct instance)
+    ///* 'key: &T::Key' - the key value to search above
+    ///
+    ///# Returns
+    ///
+    ///* 'Option<&T>' -
+    ///  * if Some - the data object with the smallest key greater than key
+    ///  * if None - no key in the tree is greater than key
+    ///
+    pub fn successor(&self, key: &T::Key) -> Option<&T> {
+        let mut current = self.root.as_ref();
+        let mut closest = None;
+        while let Some(node) = current {
+            if node.data.get_key() > key {
+                // a candidate, a closer one may be in the left subtree
+                closest = Some(&node.data);
+                current = node.left.as_ref();
+            } else {
+                current = node.right.as_ref();
+            }
+        }
+        closest
     }
     /// In order traversal initiation method
     ///
@@ -400,7 +579,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///# Arguments
     ///
     ///* '&self' - a reference to self, the AVLTree<T>
-    ///* 'key: i32' - the provided key value used to search
+    ///* 'key: &T::Key' - the provided key value used to search
     ///         through the keys values of AVLTree nodes
     ///
     ///# Returns
@@ -409,12 +588,9 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///* if match found, returns the data object stored in the node (a Client)
     ///* if no match found, returns ApplicationError:NoMatch & appropriate error message
     ///
-    pub fn find(&self, key: i32) -> Result<&T, ApplicationError> {
+    pub fn find(&self, key: &T::Key) -> Result<&T, ApplicationError> {
         self.find_value(&self.root, key).ok_or_else(|| {
-            ApplicationError::NoMatchError(format!(
-                "No client match found for the provided ID: {}",
-                key
-            ))
+            ApplicationError::NoMatchError(format!("No entry found for key {:?}", key))
         })
     }
     /// Function implementing the method to find a specific value in tree
@@ -433,7 +609,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///* 'current_node: &'a Option<Box<Node<T>>>' - optional reference to the
     ///                 box containing the current node (with data)
     ///                 we are processing in the tree.
-    ///* 'target_key: i32' - the key / id value we are searching the tree for
+    ///* 'target_key: &T::Key' - the key / id value we are searching the tree for
     ///
     ///# Returns
     ///
@@ -459,11 +635,11 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     fn find_value<'a>(
         &'a self,
         current_node: &'a Option<Box<Node<T>>>,
-        target_key: i32,
+        target_key: &T::Key,
     ) -> Option<&'a T> {
         match current_node {
             None => None,
-            Some(node) => match target_key.cmp(&node.data.get_key()) {
+            Some(node) => match target_key.cmp(node.data.get_key()) {
                 // https://doc.rust-lang.org/std/cmp/enum.Ordering.html
                 std::cmp::Ordering::Equal => Some(&node.data),
                 std::cmp::Ordering::Less => self.find_value(&node.left, target_key),
@@ -471,6 +647,134 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
             },
         }
     }
+    /// Updates the data object with the provided key in place
+    ///
+    /// the closure is called with the stored data object, no nodes are
+    /// removed or re-inserted, so no rebalancing takes place. The tree is
+    /// ordered by the key, so a closure that changes the key is undone.
+    ///
+    /// provided instead of a public get_mut: a `&mut T` handed to the caller
+    /// could change the key with nothing left to check it, so the mutable
+    /// access is scoped to the closure & the key is checked once it returns.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (AVLTree)
+    ///* 'key: &T::Key' - the key value used to search through the tree
+    ///* 'update: F' - closure called with the matching data object
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok once the data object was updated
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::NoMatchError' - no data object has the key
+    ///* 'ApplicationError::InputError' - the closure changed the key, the data
+    ///  object was restored to its value before the closure was called
+    ///
+    pub fn update<F: FnOnce(&mut T)>(
+        &mut self,
+        key: &T::Key,
+        update: F,
+    ) -> Result<(), ApplicationError> {
+        match self.find_mut(key) {
+            Some(data) => modify_keeping_key(data, update),
+            None => Err(ApplicationError::NoMatchError(format!(
+                "No entry found for key {:?}",
+                key
+            ))),
+        }
+    }
+    // finds the data object with the provided key, for the in place updates.
+    // kept private, a caller changing the key through it would break the tree order
+    fn find_mut(&mut self, key: &T::Key) -> Option<&mut T> {
+        let mut current = self.root.as_mut();
+        while let Some(node) = current {
+            match key.cmp(node.data.get_key()) {
+                // https://doc.rust-lang.org/std/cmp/enum.Ordering.html
+                std::cmp::Ordering::Equal => return Some(&mut node.data),
+                std::cmp::Ordering::Less => current = node.left.as_mut(),
+                std::cmp::Ordering::Greater => current = node.right.as_mut(),
+            }
+        }
+        None
+    }
+    /// Replaces an existing data object with one that has the same key
+    ///
+    /// the node keeps its position in the tree, only its data is swapped,
+    /// so the tree is never left without the entry.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (AVLTree)
+    ///* 'data: T' - the new data object, replaces the one with key data.get_key()
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<T, ApplicationError>'
+    ///  * On success return:
+    ///    * 'T' the replaced data object (Client object)
+    ///  * On failure return:
+    ///    * 'ApplicationError::NoMatchError' when no data object has the key,
+    ///      the tree is unchanged
+    ///
+    pub fn replace(&mut self, data: T) -> Result<T, ApplicationError> {
+        let key = data.get_key().clone();
+        match self.find_mut(&key) {
+            Some(existing) => Ok(std::mem::replace(existing, data)),
+            None => Err(ApplicationError::NoMatchError(format!(
+                "No entry found for key {:?}",
+                key
+            ))),
+        }
+    }
+    /// Replaces the data object with the same key, or inserts it when there is none
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (AVLTree)
+    ///* 'data: T' - the data object to store
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<T>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Some(T)) - the replaced data object, updated in place
+    ///    * Ok(None) - no data object had the key, a new node was inserted
+    ///  * on failure:
+    ///    * ApplicationError from the insertion
+    ///
+    pub fn upsert(&mut self, data: T) -> Result<Option<T>, ApplicationError> {
+        if let Some(existing) = self.find_mut(data.get_key()) {
+            return Ok(Some(std::mem::replace(existing, data)));
+        }
+        self.insert(data)?;
+        Ok(None)
+    }
+    /// Gets the entry of the provided key, for in place updates or insertion
+    ///
+    /// similar to the HashMap entry API, e.g.
+    /// `avl_tree.entry(id).and_modify(|client| ...)?.or_insert_with(|| client)`
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (AVLTree)
+    ///* 'key: T::Key' - the key value of the entry
+    ///
+    ///# Returns
+    ///
+    ///* 'Entry<T>' -
+    ///  * Entry::Occupied - a data object with the key exists
+    ///  * Entry::Vacant - no data object has the key
+    ///
+    pub fn entry(&mut self, key: T::Key) -> Entry<'_, T> {
+        if self.find_value(&self.root, &key).is_some() {
+            Entry::Occupied(OccupiedEntry { tree: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { tree: self, key })
+        }
+    }
     /// Public access method for AVLTree insertion implementation
     ///
     /// Provides access to the function that implements the insertion
@@ -488,9 +792,17 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///     on failure, returns Application error
     ///
     pub fn insert(&mut self, data: T) -> Result<(), ApplicationError> {
+        // insert_value takes the root, an error part way down would drop the whole tree
+        if self.find_value(&self.root, data.get_key()).is_some() {
+            return Err(ApplicationError::InputError(format!(
+                "An entry already exists with key {:?}",
+                data.get_key()
+            )));
+        }
         match Self::insert_value(self.root.take(), data) {
             Ok(new_root) => {
                 self.root = new_root;
+                self.size += 1;
                 Ok(())
             }
             Err(e) => Err(e),
@@ -503,7 +815,6 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     /// node is created with the data object, the new root node.
     /// When not empty, begins the comparison / match statements used to find it's
     /// insertion point. Uses the key value of data object T (client_id) to locate the
-
     ///
     ///# Arguments    
     ///* 'node: Option<Box<Node<T>>>' - optional box containing the current node
@@ -517,7 +828,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///     On success:
     ///         'Some(Box<Node<T>>)': updated subtree, after insertion complete & tree rebalanced
     ///     On failure:
-    ///         'ApplicationError::InputError' a client already exists with that ID
+    ///         'ApplicationError::InputError' a data object already exists with that key
     ///
     ///# Note
     ///
@@ -555,7 +866,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
                 height: 1,
             }))),
             Some(mut node) => {
-                match data.get_key().cmp(&node.data.get_key()) {
+                match data.get_key().cmp(node.data.get_key()) {
                     // https://doc.rust-lang.org/std/cmp/enum.Ordering.html
                     std::cmp::Ordering::Less => {
                         node.left = Self::insert_value(node.left.take(), data)?;
@@ -565,7 +876,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
                     }
                     std::cmp::Ordering::Equal => {
                         return Err(ApplicationError::InputError(format!(
-                            "Client already exists with the ID value: {}",
+                            "An entry already exists with key {:?}",
                             data.get_key()
                         )));
                     }
@@ -582,7 +893,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///# Arguments
     ///
     ///* '&mut self' - mutable reference to self (AVLTree)
-    ///* 'key: i32' - the key value of node / data object (Client) to remove
+    ///* 'key: &T::Key' - the key value of node / data object (Client) to remove
     ///
     ///# Returns
     ///
@@ -590,17 +901,17 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///*    On success return:
     ///         'T' the value (Client object) from the deleted node
     ///*    On failure return:
-    ///         'ApplicationError::NoMatchError' when no client for provided key found
+    ///         'ApplicationError::NoMatchError' when no data object has the provided key
     ///
-    pub fn remove(&mut self, key: i32) -> Result<T, ApplicationError> {
+    pub fn remove(&mut self, key: &T::Key) -> Result<T, ApplicationError> {
+        // remove_node takes the root, an error part way down would drop the whole tree
+        self.find(key)?;
         let (new_root, deleted_value) = Self::remove_node(self.root.take(), key)?;
         self.root = new_root;
+        self.size -= 1;
         deleted_value // return
             .ok_or_else(|| {
-                ApplicationError::NoMatchError(format!(
-                    "No matching client found with ID value: {}",
-                    key
-                ))
+                ApplicationError::NoMatchError(format!("No entry found for key {:?}", key))
             })
     }
     /// Removes a node by the provided key value (if exists)
@@ -612,7 +923,7 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///# Arguments
     ///
     ///* 'node: Option<Box<Node<T>>>' - the current node (root of this current subtree)
-    ///* 'key: i32' - key value of target node to be removed (client_id)
+    ///* 'key: &T::Key' - key value of target node to be removed (client_id)
     ///
     ///# Returns
     ///
@@ -655,15 +966,15 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
     ///
     fn remove_node(
         node: Option<Box<Node<T>>>,
-        key: i32,
+        key: &T::Key,
     ) -> Result<(Option<Box<Node<T>>>, Option<T>), ApplicationError> {
         match node {
             None => Err(ApplicationError::NoMatchError(format!(
-                "No client match was found for provided ID value: {}",
+                "No entry found for key {:?}",
                 key
             ))),
             Some(mut node) => {
-                let (new_subtree, deleted_value) = match key.cmp(&node.data.get_key()) {
+                let (new_subtree, deleted_value) = match key.cmp(node.data.get_key()) {
                     // https://doc.rust-lang.org/std/cmp/enum.Ordering.html
                     std::cmp::Ordering::Less => {
                         let (new_left, value) = Self::remove_node(node.left.take(), key)?;
@@ -727,3 +1038,622 @@ impl<T: Ord + Clone + Identification + std::fmt::Debug> AVLTree<T> {
         }
     }
 }
+
+/// applies an in place update to a data object stored in an AVLTree
+///
+/// the tree is ordered by the data object's key, so an update that
+/// changes the key is undone, and reported as an error.
+///
+///# Arguments
+///
+///* 'data: &mut T' - the data object stored in the node
+///* 'update: F' - closure called with the data object
+///
+///# Returns
+///
+///* 'Result<(), ApplicationError>' - Ok when the key is unchanged, otherwise InputError
+///
+fn modify_keeping_key<T, F>(data: &mut T, update: F) -> Result<(), ApplicationError>
+where
+    T: Clone + Identification + std::fmt::Debug,
+    F: FnOnce(&mut T),
+{
+    let original = data.clone();
+    update(data);
+    if data.get_key() != original.get_key() {
+        let changed_key = data.get_key().clone();
+        *data = original;
+        return Err(ApplicationError::InputError(format!(
+            "The update changed the key {:?} to {:?}, the key of a stored entry cannot change",
+            data.get_key(),
+            changed_key
+        )));
+    }
+    Ok(())
+}
+
+/// Represents a single key of an AVLTree<T>, which may or may not hold a data object
+///
+/// Created by AVLTree::entry.
+///
+///# Variants
+///
+///* `Occupied` - a data object with the key exists in the tree
+///* `Vacant` - no data object has the key, one may be inserted
+///
+pub enum Entry<'a, T: 'a + Identification> {
+    Occupied(OccupiedEntry<'a, T>),
+    Vacant(VacantEntry<'a, T>),
+}
+
+/// Represents an occupied entry, holds the tree & the key found in it
+///
+/// the vacant case needs the whole tree, so the entry keeps the tree rather
+/// than a reference into one of its nodes. The methods go through the tree's
+/// own lookups, which report a missing key as ApplicationError::NoMatchError.
+///
+///# Fields
+///
+///* `tree: &mut AVLTree<T>` - the tree of the entry
+///* `key: T::Key` - the key of the existing data object
+///
+pub struct OccupiedEntry<'a, T: 'a + Identification> {
+    tree: &'a mut AVLTree<T>,
+    key: T::Key,
+}
+
+/// Represents a vacant entry, holds the tree the data object may be inserted into
+///
+///# Fields
+///
+///* `tree: &mut AVLTree<T>` - the tree of the entry
+///* `key: T::Key` - the key no data object has yet
+///
+pub struct VacantEntry<'a, T: 'a + Identification> {
+    tree: &'a mut AVLTree<T>,
+    key: T::Key,
+}
+
+impl<'a, T: Clone + Identification + std::fmt::Debug> Entry<'a, T> {
+    /// returns the key of the entry
+    pub fn key(&self) -> T::Key {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key.clone(),
+            Entry::Vacant(ref entry) => entry.key.clone(),
+        }
+    }
+    /// Updates the data object in place when the entry is occupied
+    ///
+    ///# Arguments
+    ///
+    ///* 'self' - the entry
+    ///* 'update: F' - closure called with the existing data object
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Self, ApplicationError>' - the entry, for a following or_insert_with
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::InputError' - the closure changed the key, the data
+    ///  object was restored to its value before the closure was called
+    ///
+    pub fn and_modify<F: FnOnce(&mut T)>(mut self, update: F) -> Result<Self, ApplicationError> {
+        if let Entry::Occupied(ref mut entry) = self {
+            entry.update(update)?;
+        }
+        Ok(self)
+    }
+    /// Returns the data object, inserting the one from the closure when the entry is vacant
+    ///
+    ///# Arguments
+    ///
+    ///* 'self' - the entry
+    ///* 'default: F' - closure creating the data object, only called for a vacant entry
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<&T, ApplicationError>' - the existing or newly inserted data object
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::InputError' - the created data object's key is not the entry's
+    ///
+    pub fn or_insert_with<F: FnOnce() -> T>(self, default: F) -> Result<&'a T, ApplicationError> {
+        match self {
+            Entry::Occupied(entry) => entry.into_ref(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+}
+
+impl<'a, T: Clone + Identification + std::fmt::Debug> OccupiedEntry<'a, T> {
+    /// returns the key of the entry
+    pub fn key(&self) -> &T::Key {
+        &self.key
+    }
+    /// returns a reference to the existing data object
+    pub fn get(&self) -> Result<&T, ApplicationError> {
+        self.tree.find(&self.key)
+    }
+    /// converts the entry into a reference with the lifetime of the tree borrow
+    pub fn into_ref(self) -> Result<&'a T, ApplicationError> {
+        let OccupiedEntry { tree, key } = self;
+        let tree: &'a AVLTree<T> = tree;
+        tree.find(&key)
+    }
+    /// Updates the existing data object in place
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to the entry
+    ///* 'update: F' - closure called with the existing data object
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok once the data object was updated
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::InputError' - the closure changed the key, the data
+    ///  object was restored to its value before the closure was called
+    ///* 'ApplicationError::NoMatchError' - the key is no longer in the tree
+    ///
+    pub fn update<F: FnOnce(&mut T)>(&mut self, update: F) -> Result<(), ApplicationError> {
+        self.tree.update(&self.key, update)
+    }
+    /// Replaces the existing data object in place
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to the entry
+    ///* 'data: T' - the new data object, with the same key
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<T, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(T) - the replaced data object
+    ///  * on failure:
+    ///    * ApplicationError::InputError - the new data object has a different key,
+    ///      the entry is unchanged
+    ///    * ApplicationError::NoMatchError - the key is no longer in the tree
+    ///
+    pub fn insert(&mut self, data: T) -> Result<T, ApplicationError> {
+        if *data.get_key() != self.key {
+            return Err(ApplicationError::InputError(format!(
+                "Key {:?} does not match the entry's key {:?}",
+                data.get_key(),
+                self.key
+            )));
+        }
+        self.tree.replace(data)
+    }
+}
+
+impl<'a, T: Clone + Identification + std::fmt::Debug> VacantEntry<'a, T> {
+    /// returns the key of the entry
+    pub fn key(&self) -> &T::Key {
+        &self.key
+    }
+    /// Inserts the data object into the tree at the entry's key
+    ///
+    ///# Arguments
+    ///
+    ///* 'self' - the entry
+    ///* 'data: T' - the new data object, with the entry's key
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<&T, ApplicationError>' - the inserted data object
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::InputError' - the data object has a different key,
+    ///  nothing was inserted
+    ///
+    pub fn insert(self, data: T) -> Result<&'a T, ApplicationError> {
+        if *data.get_key() != self.key {
+            return Err(ApplicationError::InputError(format!(
+                "Key {:?} does not match the entry's key {:?}",
+                data.get_key(),
+                self.key
+            )));
+        }
+        let VacantEntry { tree, key } = self;
+        tree.insert(data)?;
+        // the insertion may rotate the node, so it is found by its key
+        let tree: &'a AVLTree<T> = tree;
+        tree.find(&key)
+    }
+}
+
+impl<'a, T: Identification> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    /// Yields the next data object in key order
+    ///
+    ///# Behavior
+    ///
+    ///* 1. pops the node on top of the stack, the smallest key not yet yielded
+    ///* 2. when its key is past the upper bound, clears the stack, every
+    ///     remaining key is larger, so the iterator is finished
+    ///* 3. otherwise pushes its right child & that child's left descendants,
+    ///     the next smallest keys, then yields the popped node's data
+    ///
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        let key = node.data.get_key();
+        let before_end = match self.end {
+            Bound::Included(ref end) => key <= end,
+            Bound::Excluded(ref end) => key < end,
+            Bound::Unbounded => true,
+        };
+        if !before_end {
+            self.stack.clear();
+            return None;
+        }
+        let mut current = node.right.as_ref();
+        while let Some(child) = current {
+            self.stack.push(child);
+            current = child.left.as_ref();
+        }
+        Some(&node.data)
+    }
+}
+
+impl<'a, T: Clone + Identification + std::fmt::Debug> IntoIterator for &'a AVLTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    /// allows `for client in &avl_tree`, the same as avl_tree.iter()
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Represents a secondary index over the data objects of an AVLTree<T>
+///
+/// Maps a value computed from each data object (e.g. a Client's assigned
+/// employee) to the keys (client_id values) of every data object with that
+/// value. The index does not hold the data objects, they are looked up in the
+/// tree by key. The owner of the tree & index keeps both in sync, calling
+/// insert / remove on the index for every change to the tree.
+///
+///# Fields
+///
+///* `index_key: fn(&T) -> K` - computes the indexed value of a data object
+///* `entries: BTreeMap<K, BTreeSet<T::Key>>` - each indexed value, and the sorted
+///  keys of the data objects with it. Values without data objects are removed.
+///
+pub struct SecondaryIndex<T: Identification, K: Ord> {
+    index_key: fn(&T) -> K,
+    entries: BTreeMap<K, BTreeSet<T::Key>>,
+}
+
+impl<T: Identification, K: Ord> SecondaryIndex<T, K> {
+    /// Creates a new, empty index
+    ///
+    ///# Arguments
+    ///
+    ///* 'index_key: fn(&T) -> K' - computes the indexed value of a data object
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - the empty SecondaryIndex
+    ///
+    pub fn new(index_key: fn(&T) -> K) -> Self {
+        SecondaryIndex {
+            index_key,
+            entries: BTreeMap::new(),
+        }
+    }
+    /// removes every entry from the index
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+    /// adds the key of a data object under its indexed value
+    pub fn insert(&mut self, data: &T) {
+        self.entries
+            .entry((self.index_key)(data))
+            .or_default()
+            .insert(data.get_key().clone());
+    }
+    /// Removes the key of a data object from under its indexed value
+    ///
+    /// must be called with the data object as it was indexed, before any
+    /// change to its indexed value. The indexed value is removed with its
+    /// last key, so get never returns an empty set.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self (SecondaryIndex)
+    ///* 'data: &T' - the data object being removed / changed
+    ///
+    pub fn remove(&mut self, data: &T) {
+        let value = (self.index_key)(data);
+        let now_empty = match self.entries.get_mut(&value) {
+            Some(keys) => {
+                keys.remove(data.get_key());
+                keys.is_empty()
+            }
+            None => false,
+        };
+        if now_empty {
+            self.entries.remove(&value);
+        }
+    }
+    /// Returns the keys of the data objects with the indexed value
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - reference to self (SecondaryIndex)
+    ///* 'value: &K' - the indexed value to look up
+    ///
+    ///# Returns
+    ///
+    ///* 'Option<&BTreeSet<T::Key>>' -
+    ///  * if Some - the sorted keys, never empty
+    ///  * if None - no data object has the value
+    ///
+    pub fn get(&self, value: &K) -> Option<&BTreeSet<T::Key>> {
+        self.entries.get(value)
+    }
+    /// returns every indexed value with at least one data object, in ascending order
+    pub fn values(&self) -> impl Iterator<Item = &K> {
+        self.entries.keys()
+    }
+    /// returns every indexed value & its keys, in ascending order of the value
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &BTreeSet<T::Key>)> {
+        self.entries.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(id: i32, name: &str) -> Client {
+        Client::new(id, name.to_string(), 1, 1)
+    }
+
+    fn tree_of(ids: &[i32]) -> AVLTree<Client> {
+        let mut tree = AVLTree::new();
+        for &id in ids {
+            tree.insert(client(id, "Client")).unwrap();
+        }
+        tree
+    }
+
+    fn keys(tree: &AVLTree<Client>) -> Vec<i32> {
+        tree.iter().map(|client| client.get_client_id()).collect()
+    }
+
+    // a data object keyed by a String, for the non-i32 key tests
+    #[derive(Clone, Debug)]
+    struct Account {
+        code: String,
+        balance: i32,
+    }
+
+    impl Identification for Account {
+        type Key = String;
+
+        fn get_key(&self) -> &String {
+            &self.code
+        }
+    }
+
+    fn account(code: &str, balance: i32) -> Account {
+        Account {
+            code: code.to_string(),
+            balance,
+        }
+    }
+
+    #[test]
+    fn update_changes_data_in_place() {
+        let mut tree = tree_of(&[2, 1, 3]);
+        tree.update(&2, |client| client.change_client_service(4))
+            .unwrap();
+        assert_eq!(tree.find(&2).unwrap().get_client_service(), 4);
+        assert!(tree.update(&9, |_| {}).is_err());
+    }
+
+    #[test]
+    fn update_changing_key_is_undone() {
+        let mut tree = tree_of(&[1, 2, 3]);
+        let result = tree.update(&2, |client| {
+            client.set_client_id(10);
+            client.change_client_service(4);
+        });
+        assert!(result.is_err());
+        let stored = tree.find(&2).unwrap();
+        assert_eq!(stored.get_client_service(), 1);
+        assert_eq!(keys(&tree), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn entry_and_modify_keeps_key() {
+        let mut tree = tree_of(&[1, 2]);
+        assert!(tree
+            .entry(1)
+            .and_modify(|client| client.set_client_id(5))
+            .is_err());
+        assert_eq!(keys(&tree), vec![1, 2]);
+
+        let stored = tree
+            .entry(1)
+            .and_modify(|client| client.change_client_service(3))
+            .unwrap()
+            .or_insert_with(|| client(1, "Unused"))
+            .unwrap();
+        assert_eq!(stored.get_client_service(), 3);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn entry_inserts_vacant_key() {
+        let mut tree = tree_of(&[1]);
+        // a data object with another key is refused, the tree is kept
+        assert!(tree.entry(2).or_insert_with(|| client(3, "Wrong")).is_err());
+        assert_eq!(keys(&tree), vec![1]);
+
+        tree.entry(2).or_insert_with(|| client(2, "Ada")).unwrap();
+        assert_eq!(keys(&tree), vec![1, 2]);
+        assert_eq!(tree.len(), 2);
+    }
+
+    #[test]
+    fn upsert_replaces_or_inserts() {
+        let mut tree = tree_of(&[1, 2]);
+        let replaced = tree.upsert(client(2, "Ada")).unwrap();
+        assert_eq!(replaced.unwrap().get_client_name(), "Client");
+        assert_eq!(tree.find(&2).unwrap().get_client_name(), "Ada");
+        assert!(tree.upsert(client(3, "Grace")).unwrap().is_none());
+        assert_eq!(keys(&tree), vec![1, 2, 3]);
+        assert_eq!(tree.len(), 3);
+    }
+
+    #[test]
+    fn failed_insert_and_remove_keep_tree() {
+        let mut tree = tree_of(&[1, 2, 3, 4, 5]);
+        assert!(tree.insert(client(3, "Duplicate")).is_err());
+        assert!(tree.remove(&9).is_err());
+        assert_eq!(keys(&tree), vec![1, 2, 3, 4, 5]);
+        assert_eq!(tree.len(), 5);
+        // replace never inserts a missing key
+        assert!(tree.replace(client(9, "Missing")).is_err());
+        assert_eq!(tree.len(), 5);
+    }
+
+    #[test]
+    fn iter_yields_keys_in_order() {
+        let tree = tree_of(&[50, 20, 80, 10, 30, 70, 90, 25]);
+        assert_eq!(keys(&tree), vec![10, 20, 25, 30, 50, 70, 80, 90]);
+        assert_eq!(tree.len(), 8);
+        assert_eq!((&tree).into_iter().count(), 8);
+        assert_eq!(AVLTree::<Client>::new().iter().count(), 0);
+    }
+
+    #[test]
+    fn range_honours_each_bound_kind() {
+        let tree = tree_of(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        let range_keys = |range: (Bound<i32>, Bound<i32>)| -> Vec<i32> {
+            tree.range(range)
+                .map(|client| client.get_client_id())
+                .collect()
+        };
+        assert_eq!(
+            range_keys((Bound::Included(3), Bound::Included(5))),
+            vec![3, 4, 5]
+        );
+        assert_eq!(
+            range_keys((Bound::Excluded(3), Bound::Excluded(5))),
+            vec![4]
+        );
+        assert_eq!(
+            range_keys((Bound::Unbounded, Bound::Excluded(3))),
+            vec![1, 2]
+        );
+        assert_eq!(
+            range_keys((Bound::Included(7), Bound::Unbounded)),
+            vec![7, 8]
+        );
+        assert_eq!(range_keys((Bound::Unbounded, Bound::Unbounded)).len(), 8);
+        // bounds between & past the stored keys
+        assert!(range_keys((Bound::Included(9), Bound::Unbounded)).is_empty());
+        assert!(range_keys((Bound::Excluded(4), Bound::Excluded(5))).is_empty());
+
+        let from: Vec<i32> = tree
+            .range(6..)
+            .map(|client| client.get_client_id())
+            .collect();
+        assert_eq!(from, vec![6, 7, 8]);
+        let to: Vec<i32> = tree
+            .range(..=2)
+            .map(|client| client.get_client_id())
+            .collect();
+        assert_eq!(to, vec![1, 2]);
+    }
+
+    #[test]
+    fn min_and_max() {
+        let tree = tree_of(&[5, 3, 9, 1, 7]);
+        assert_eq!(tree.min().unwrap().get_client_id(), 1);
+        assert_eq!(tree.max().unwrap().get_client_id(), 9);
+        let empty: AVLTree<Client> = AVLTree::new();
+        assert!(empty.min().is_none());
+        assert!(empty.max().is_none());
+    }
+
+    #[test]
+    fn predecessor_and_successor() {
+        let tree = tree_of(&[10, 20, 30, 40, 50]);
+        let id = |found: Option<&Client>| found.map(|client| client.get_client_id());
+        assert_eq!(id(tree.predecessor(&30)), Some(20));
+        assert_eq!(id(tree.successor(&30)), Some(40));
+        // keys that are not stored still have neighbours
+        assert_eq!(id(tree.predecessor(&35)), Some(30));
+        assert_eq!(id(tree.successor(&35)), Some(40));
+        assert_eq!(id(tree.predecessor(&10)), None);
+        assert_eq!(id(tree.successor(&50)), None);
+        assert_eq!(id(tree.predecessor(&99)), Some(50));
+        assert_eq!(id(tree.successor(&0)), Some(10));
+    }
+
+    #[test]
+    fn len_follows_insert_and_remove() {
+        let mut tree = tree_of(&[1, 2, 3]);
+        tree.remove(&2).unwrap();
+        assert_eq!(tree.len(), 2);
+        assert!(tree.remove(&2).is_err());
+        assert_eq!(tree.len(), 2);
+        assert!(tree.insert(client(1, "Duplicate")).is_err());
+        assert_eq!(tree.len(), 2);
+        tree.clear();
+        assert_eq!(tree.len(), 0);
+        assert!(tree.is_empty());
+    }
+
+    #[test]
+    fn string_keys_are_ordered_and_found() {
+        let mut tree = AVLTree::new();
+        for code in &["delta", "alpha", "echo", "charlie", "bravo"] {
+            tree.insert(account(code, 0)).unwrap();
+        }
+        let codes: Vec<&str> = tree.iter().map(|account| account.code.as_str()).collect();
+        assert_eq!(codes, vec!["alpha", "bravo", "charlie", "delta", "echo"]);
+        // the errors name the key, not a client
+        assert_eq!(
+            tree.insert(account("charlie", 5)).unwrap_err().to_string(),
+            ApplicationError::InputError(
+                "An entry already exists with key \"charlie\"".to_string()
+            )
+            .to_string()
+        );
+        assert_eq!(
+            tree.find(&"zulu".to_string()).unwrap_err().to_string(),
+            ApplicationError::NoMatchError("No entry found for key \"zulu\"".to_string())
+                .to_string()
+        );
+
+        tree.update(&"charlie".to_string(), |account| account.balance = 10)
+            .unwrap();
+        assert_eq!(tree.find(&"charlie".to_string()).unwrap().balance, 10);
+        let changed = tree.update(&"bravo".to_string(), |account| {
+            account.code = "zulu".to_string();
+        });
+        assert!(changed.is_err());
+        assert!(tree.find(&"bravo".to_string()).is_ok());
+
+        let from_c: Vec<&str> = tree
+            .range("c".to_string().."e".to_string())
+            .map(|account| account.code.as_str())
+            .collect();
+        assert_eq!(from_c, vec!["charlie", "delta"]);
+        assert_eq!(tree.successor(&"c".to_string()).unwrap().code, "charlie");
+        assert_eq!(tree.remove(&"alpha".to_string()).unwrap().code, "alpha");
+        assert_eq!(tree.len(), 4);
+    }
+}
@@ -18,16 +18,38 @@ use config::{Config, File};
 // imports the Path struct from the standard library path module
 use log::debug;
 use std::borrow::Cow;
+use std::cell::{RefCell, RefMut};
+use std::collections::BTreeMap;
+use std::convert::TryInto;
 use std::env;
+use std::ops::{Deref, DerefMut};
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
 //use std::task::Context;
 // imports the Queryable trait from the mysql crate, prelude module
 use mysql::prelude::*;
 // imports all public items from the mysql crate
-use mysql::{params, OptsBuilder, Pool, SslOpts};
+use mysql::{params, OptsBuilder, Pool, PooledConn, SslOpts};
+// imports the sqlite connection & optional row helper, only with the sqlite feature
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, OptionalExtension};
+// imports the once-initialized cell holding each sqlite handle's connection
+#[cfg(feature = "sqlite")]
+use std::cell::OnceCell;
+// imports Duration, for how long a sqlite connection waits on another's lock
+#[cfg(feature = "sqlite")]
+use std::time::Duration;
 
+// imports the AuditEntry struct from the audit module
+use crate::audit::AuditEntry;
+// imports the LoginAttempts struct from the auth module
+use crate::auth::LoginAttempts;
+// imports the client history items from the client_history module
+use crate::client_history::{ClientHistoryEntry, HistoryAction, HistoryFilter};
 // imports all public items from the firm_models module
 use crate::firm_models::*;
+// imports the Migration definition from the migrations module
+use crate::migrations::Migration;
 // imports necessary errors from errors module
 use crate::errors::{ApplicationError, DatabaseError};
 //
@@ -45,11 +67,80 @@ use crate::errors::{ApplicationError, DatabaseError};
 ///# Fields
 ///
 ///* `pool: Pool` - A connection pool used to manage db connections
+///* `transaction_conn: Option<RefCell<PooledConn>>` - the connection owned by a
+///  transaction handle (begin_transaction). While Some, every query made
+///  through this handle runs on it. None for MySqlDatabase::new
 ///
-#[derive(Clone, Debug)]
+#[derive(Debug)]
 pub struct MySqlDatabase {
     pool: Pool,
-    // populate with db related
+    // RefCell since the read only queries (&self) also need the transaction's connection
+    transaction_conn: Option<RefCell<PooledConn>>,
+}
+
+impl Clone for MySqlDatabase {
+    /// Clones the MySqlDatabase, sharing the connection pool
+    ///
+    /// A transaction belongs to the handle that holds its connection, so
+    /// the clone never inherits it.
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - new MySqlDatabase using the same pool, no open transaction
+    ///
+    fn clone(&self) -> Self {
+        MySqlDatabase {
+            pool: self.pool.clone(),
+            transaction_conn: None,
+        }
+    }
+}
+
+impl Drop for MySqlDatabase {
+    /// Rolls back a transaction handle dropped while still open
+    ///
+    /// keeps a connection with an unfinished transaction from being
+    /// handed back to the pool.
+    fn drop(&mut self) {
+        if self.in_transaction() {
+            let _ = self.rollback_transaction();
+        }
+    }
+}
+
+/// Represents the connection a MySqlDatabase query runs on
+///
+/// Either the connection owned by a transaction handle, or a
+/// connection freshly taken from the pool for a single query.
+///
+///# Variants
+///
+///* `Pinned` - borrow of the transaction handle's connection
+///* `Pooled` - connection from the pool, returned to it when dropped
+///
+enum MySqlConn<'a> {
+    Pinned(RefMut<'a, PooledConn>),
+    Pooled(PooledConn),
+}
+
+impl<'a> Deref for MySqlConn<'a> {
+    type Target = PooledConn;
+
+    fn deref(&self) -> &PooledConn {
+        match *self {
+            MySqlConn::Pinned(ref conn) => conn,
+            MySqlConn::Pooled(ref conn) => conn,
+        }
+    }
+}
+
+impl<'a> DerefMut for MySqlConn<'a> {
+    fn deref_mut(&mut self) -> &mut PooledConn {
+        match *self {
+            MySqlConn::Pinned(ref mut conn) => conn,
+            MySqlConn::Pooled(ref mut conn) => conn,
+        }
+    }
 }
 
 impl MySqlDatabase {
@@ -149,17 +240,43 @@ impl MySqlDatabase {
         //
         debug!("DB connected successfully."); // for troubleshooting / logging
 
-        Ok(MySqlDatabase { pool })
+        Ok(MySqlDatabase {
+            pool,
+            transaction_conn: None,
+        })
+    }
+    /// Gets the connection the next query should run on
+    ///
+    /// returns the transaction's connection when this is a transaction
+    /// handle, otherwise takes a connection from the pool.
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<MySqlConn, DatabaseError>' - the connection, or ConnectionError
+    ///
+    fn conn(&self) -> Result<MySqlConn<'_>, DatabaseError> {
+        if let Some(ref conn) = self.transaction_conn {
+            return Ok(MySqlConn::Pinned(conn.borrow_mut()));
+        }
+        self.pool
+            .get_conn()
+            .map(MySqlConn::Pooled)
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))
+    }
+    // releases the transaction's connection, used to finish the transaction on it
+    fn take_transaction_conn(&mut self) -> Result<PooledConn, DatabaseError> {
+        self.transaction_conn
+            .take()
+            .map(RefCell::into_inner)
+            .ok_or_else(|| {
+                DatabaseError::TransactionError("No transaction is in progress".to_string())
+            })
     }
     // split to remove need to set up twice
     fn local_connection_config(
     ) -> Result<(String, String, String, u16, String, PathBuf), ApplicationError> {
         // quick setup to branch between local docker/local cargo build options
-        let path_config =
-            PathBuf::from(env::var("CONFIG_PATH").unwrap_or_else(|_| "./config.toml".to_string()));
-        let config = Config::builder()
-            .add_source(File::from(path_config).required(true))
-            .build()?;
+        let config = load_config(true)?;
         // exported to remove duplicate definition
         Ok((
             config.get_string("database.username")?,
@@ -171,6 +288,192 @@ impl MySqlDatabase {
         ))
     }
 }
+/// Opens the database backend selected for this environment
+///
+/// Reads the ENVIRONMENT variable to pick which DatabaseManager
+/// implementation the application runs against:
+/// - "memory" selects the process local InMemoryDatabase
+/// - "sqlite" selects the single file SqliteDatabase (sqlite feature)
+/// - "production" always uses the MySqlDatabase
+/// - any other value, or no value, uses the `database.backend` key of
+///   config.toml when present, and the MySqlDatabase otherwise
+///
+///# Returns
+///
+///* 'Result<Box<dyn DatabaseManager>, ApplicationError>' -
+///  * on success:
+///    * Ok(Box<dyn DatabaseManager>) - the boxed, connected database backend
+///  * on fail:
+///    * ApplicationError - the error returned by the backend constructor,
+///      or ConfigError for an unknown / unavailable backend
+///
+pub fn open_database() -> Result<Box<dyn DatabaseManager>, ApplicationError> {
+    let backend = match env::var("ENVIRONMENT") {
+        Ok(ref env) if env.as_str() == "production" => "mysql".to_string(),
+        Ok(ref env) if env.as_str() == "memory" || env.as_str() == "sqlite" => env.clone(),
+        _ => load_config(false)?
+            .get_string("database.backend")
+            .unwrap_or_else(|_| "mysql".to_string()),
+    };
+
+    match backend.as_str() {
+        "memory" => {
+            debug!("Using in-memory database backend.");
+            Ok(Box::new(InMemoryDatabase::new()))
+        }
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            debug!("Using sqlite database backend.");
+            Ok(Box::new(SqliteDatabase::new()?))
+        }
+        #[cfg(not(feature = "sqlite"))]
+        "sqlite" => Err(ApplicationError::ConfigError(
+            "sqlite backend selected, but this build does not include the sqlite feature"
+                .to_string(),
+        )),
+        "mysql" => Ok(Box::new(MySqlDatabase::new()?)),
+        other => Err(ApplicationError::ConfigError(format!(
+            "Unknown database backend: {}",
+            other
+        ))),
+    }
+}
+
+/// Loads the config.toml configuration file
+///
+/// Uses the CONFIG_PATH variable when set, otherwise ./config.toml
+///
+///# Arguments
+///
+///* 'required: bool' - whether a missing file is an error, or an empty config
+///
+///# Returns
+///
+///* 'Result<Config, ApplicationError>' - the loaded config, or ConfigError
+///
+pub fn load_config(required: bool) -> Result<Config, ApplicationError> {
+    let path_config =
+        PathBuf::from(env::var("CONFIG_PATH").unwrap_or_else(|_| "./config.toml".to_string()));
+    Ok(Config::builder()
+        .add_source(File::from(path_config).required(required))
+        .build()?)
+}
+
+/// converts a backend's last insert id into an i32 table id
+///
+/// the id columns are INT, so a generated id that does not fit
+/// an i32 means the insert cannot be mapped back to the model.
+///
+///# Arguments
+///
+///* 'id' - the last insert id reported by the backend connection
+///
+///# Returns
+///
+///* 'Result<i32, DatabaseError>' - the generated id, or QueryError when out of range
+///
+fn generated_id<T: TryInto<i32> + Copy + std::fmt::Display>(id: T) -> Result<i32, DatabaseError> {
+    id.try_into()
+        .map_err(|_| DatabaseError::QueryError(format!("Generated id out of range: {}", id)))
+}
+
+/// rebuilds an Employee from the columns of an employees row
+///
+///# Arguments
+///
+///* 'id', 'name', 'hash' - the employee_id, employee_name & hashed_password columns
+///* 'role' - the employee_role column
+///
+///# Returns
+///
+///* 'Result<Employee, DatabaseError>' - the employee, or QueryError for an unknown role
+///
+fn stored_employee(id: i32, name: &str, hash: &str, role: i32) -> Result<Employee, DatabaseError> {
+    let role = EmployeeRole::convert_i32(role).ok_or_else(|| {
+        DatabaseError::QueryError(format!("Unknown role {} for employee {}", role, id))
+    })?;
+    Ok(Employee::from_stored(id, name, hash, role))
+}
+
+/// rebuilds a ClientHistoryEntry from the columns of a client_history row
+///
+///# Arguments
+///
+///* 'row' - the occurred_at, client_id, changed_by, action, old_value
+///  & new_value columns
+///
+///# Returns
+///
+///* 'Result<ClientHistoryEntry, DatabaseError>' - the entry, or QueryError for an unknown action
+///
+fn stored_history_entry(
+    (occurred_at, client_id, changed_by, action, old_value, new_value): (i64, i32, i32, i32, i32, i32),
+) -> Result<ClientHistoryEntry, DatabaseError> {
+    let action = HistoryAction::convert_i32(action).ok_or_else(|| {
+        DatabaseError::QueryError(format!("Unknown history action {} for client {}", action, client_id))
+    })?;
+    Ok(ClientHistoryEntry {
+        occurred_at,
+        client_id,
+        changed_by,
+        action,
+        old_value,
+        new_value,
+    })
+}
+
+/// builds the client_history select query for a history search
+///
+/// both SQL backends accept `?` placeholders, so the same query and
+/// parameters are used for MySQL and SQLite. The conditions match
+/// HistoryFilter::matches.
+///
+///# Arguments
+///
+///* 'filter' - the search, unset fields match any row
+///
+///# Returns
+///
+///* '(String, Vec<i64>)' - the query, and its parameters in placeholder order
+///
+fn history_query(filter: &HistoryFilter) -> (String, Vec<i64>) {
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    if let Some(client_id) = filter.client_id {
+        conditions.push("client_id = ?".to_string());
+        values.push(i64::from(client_id));
+    }
+    if let Some(employee_id) = filter.employee_id {
+        conditions.push(format!(
+            "(changed_by = ? OR (action = {} AND (old_value = ? OR new_value = ?)))",
+            HistoryAction::Reassign as i32
+        ));
+        values.extend([i64::from(employee_id); 3]);
+    }
+    if let Some(action) = filter.action {
+        conditions.push("action = ?".to_string());
+        values.push(action as i64);
+    }
+    if let Some(from) = filter.from {
+        conditions.push("occurred_at >= ?".to_string());
+        values.push(from);
+    }
+    if let Some(until) = filter.until {
+        conditions.push("occurred_at < ?".to_string());
+        values.push(until);
+    }
+
+    let mut query = "SELECT occurred_at, client_id, changed_by, action, old_value, new_value \
+        FROM client_history"
+        .to_string();
+    if !conditions.is_empty() {
+        query.push_str(" WHERE ");
+        query.push_str(&conditions.join(" AND "));
+    }
+    query.push_str(" ORDER BY occurred_at, history_id");
+    (query, values)
+}
+
 /// definition for the interface that is used to manage
 /// database based operations in the application
 ///
@@ -184,24 +487,28 @@ impl MySqlDatabase {
 pub trait DatabaseManager {
     /// init fn to create box clone of DatabaseManager implementation.
     fn clone_box(&self) -> Box<dyn DatabaseManager>;
-    /// init fn to initiate a new transaction
-    fn begin_transaction(&mut self) -> Result<(), DatabaseError>;
-    /// init fn to commit the changes / current transaction
+    /// init fn to initiate a new transaction, returns the handle its queries run through
+    fn begin_transaction(&self) -> Result<Box<dyn DatabaseManager>, DatabaseError>;
+    /// init fn to commit the changes / current transaction of a transaction handle
     fn commit_transaction(&mut self) -> Result<(), DatabaseError>;
-    /// init fn to back data from this transaction
+    /// init fn to back data from the transaction of a transaction handle
     fn rollback_transaction(&mut self) -> Result<(), DatabaseError>;
+    /// init fn to check whether this is a transaction handle with its transaction open
+    fn in_transaction(&self) -> bool;
     /// init fn to get all clients from the database.
     fn get_clients(&self) -> Result<Vec<Client>, DatabaseError>;
-    /// init fn to save new client to database.
-    fn new_client(&mut self, client: &Client) -> Result<(), DatabaseError>;
+    /// init fn to get a single client by client_id, locking its row within a transaction
+    fn get_client(&self, client_id: i32) -> Result<Option<Client>, DatabaseError>;
+    /// init fn to save new client to database, returns the generated client_id.
+    fn new_client(&mut self, client: &Client) -> Result<i32, DatabaseError>;
     /// init fn to update client instance in database
     fn update_client(&mut self, client: &Client) -> Result<(), DatabaseError>;
     /// init fn to remove client instance from database
     fn remove_client(&mut self, client: &Client) -> Result<(), DatabaseError>;
     /// init fn to retrieve employee pass_hash from database
     fn get_employee_hash(&mut self, employee_id: i32) -> Result<Option<String>, DatabaseError>;
-    /// init fn to save employee instance in database
-    fn new_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError>;
+    /// init fn to save employee instance in database, returns the generated employee_id.
+    fn new_employee(&mut self, employee: &Employee) -> Result<i32, DatabaseError>;
     /// init fn to update employee instance in database
     fn update_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError>;
     /// init fn to remove employee instance from database
@@ -210,14 +517,37 @@ pub trait DatabaseManager {
     fn get_employees(&self) -> Result<Vec<Employee>, DatabaseError>;
     /// init fn to retrieve an employee from database
     fn get_employee(&self, employee_id: i32) -> Result<Option<Employee>, DatabaseError>;
+    /// init fn to save an audit log entry in database
+    fn new_audit_entry(&mut self, entry: &AuditEntry) -> Result<(), DatabaseError>;
+    /// init fn to save a client history entry in database
+    fn new_history_entry(&mut self, entry: &ClientHistoryEntry) -> Result<(), DatabaseError>;
+    /// init fn to search the client history, oldest entries first
+    fn get_client_history(
+        &self,
+        filter: &HistoryFilter,
+    ) -> Result<Vec<ClientHistoryEntry>, DatabaseError>;
+    /// init fn to retrieve an employee's failed login record
+    fn get_login_attempts(&self, employee_id: i32) -> Result<Option<LoginAttempts>, DatabaseError>;
+    /// init fn to add a failed attempt to an employee's failed login record, incremented
+    /// by the database, returns the updated record
+    fn record_login_failure(
+        &mut self,
+        employee_id: i32,
+        now: i64,
+    ) -> Result<LoginAttempts, DatabaseError>;
+    /// init fn to remove an employee's failed login record
+    fn clear_login_attempts(&mut self, employee_id: i32) -> Result<(), DatabaseError>;
+    /// init fn to retrieve the applied schema migration versions
+    fn applied_migrations(&mut self) -> Result<Vec<i32>, DatabaseError>;
+    /// init fn to apply & record a schema migration
+    fn apply_migration(&mut self, migration: &Migration) -> Result<(), DatabaseError>;
 }
 
 impl Clone for Box<dyn DatabaseManager> {
-    /// remove employee instance from database
-    ///
-    /// # Arguments
+    /// Clones the boxed DatabaseManager, delegating to clone_box
     ///
-    /// * `employee_id` - employee_id for this instance
+    /// the clone is a handle on the same backend, and never inherits
+    /// this handle's open transaction.
     ///
     ///# Returns
     ///
@@ -247,29 +577,45 @@ impl DatabaseManager for MySqlDatabase {
     ///
     /// # Arguments
     ///
-    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `&self` - reference to MySql database instance
     ///
     ///# Returns
     ///
-    ///* 'Result<(), DatabaseError> ' -
-    ///     on success:
-    ///         Ok(()) - the transaction successfully initiated
-    ///     on fail:
-    ///         DatabaseError::ConnectionError - if db connection fails to init
-    ///         DatabaseError::TransactionError - if the transaction query fails to init
+    ///* 'Result<Box<dyn DatabaseManager>, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(Box<dyn DatabaseManager>) - the transaction handle, owning the
+    ///      connection the transaction runs on
+    ///  * on fail:
+    ///    * DatabaseError::ConnectionError - if db connection fails to init
+    ///    * DatabaseError::TransactionError - if the transaction query fails to init
     ///
     ///# Errors
     ///
     ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
-    ///* 'DatabaseError::TransactionError' - failure to execute transaction related query on database
+    ///* 'DatabaseError::TransactionError' - this is already a transaction handle, or failure
+    ///  to execute transaction related query on database
     ///
-    fn begin_transaction(&mut self) -> Result<(), DatabaseError> {
+    ///# Note
+    ///
+    /// This instance is left unchanged, queries for the transaction must be
+    /// made through the returned handle, then committed or rolled back on it.
+    ///
+    fn begin_transaction(&self) -> Result<Box<dyn DatabaseManager>, DatabaseError> {
+        if self.in_transaction() {
+            return Err(DatabaseError::TransactionError(
+                "A transaction is already in progress".to_string(),
+            ));
+        }
         let mut conn = self
             .pool
             .get_conn()
             .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
         conn.query_drop("START TRANSACTION")
-            .map_err(|e| DatabaseError::TransactionError(e.to_string()))
+            .map_err(|e| DatabaseError::TransactionError(e.to_string()))?;
+        Ok(Box::new(MySqlDatabase {
+            pool: self.pool.clone(),
+            transaction_conn: Some(RefCell::new(conn)),
+        }))
     }
     /// attempts to commit the transaction
     ///
@@ -286,21 +632,20 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'Result<(), DatabaseError> ' -
     ///     on success:
     ///     on fail:
-    ///         ConnectionError with failure to get pool/establish connection
     ///         TransactionError on failure to commit changes for the transaction
     ///
     ///# Errors
     ///
-    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
-    ///* 'DatabaseError::TransactionError' - failure to execute transaction related query on database
+    ///* 'DatabaseError::TransactionError' - no transaction is open, or failure to execute
+    ///  transaction related query on database
     ///
     fn commit_transaction(&mut self) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
-        conn.query_drop("COMMIT")
-            .map_err(|e| DatabaseError::TransactionError(e.to_string()))
+        let mut conn = self.take_transaction_conn()?;
+        conn.query_drop("COMMIT").map_err(|e| {
+            // do not hand a connection with an open transaction back to the pool
+            let _ = conn.query_drop("ROLLBACK");
+            DatabaseError::TransactionError(e.to_string())
+        })
     }
     /// attempts to rollback the transaction
     ///
@@ -311,24 +656,29 @@ impl DatabaseManager for MySqlDatabase {
     ///# Returns
     ///
     ///* 'Result<(), DatabaseError> ' -
-    ///     on success:
-    ///         Ok(()) when the transaction successfully is rolled back
-    ///     on fail:
-    ///         ConnectionError when the database connection cannot be established
-    ///         TransactionError on failure to commit changes for the transaction
+    ///  * on success:
+    ///    * Ok(()) when the transaction successfully is rolled back
+    ///  * on fail:
+    ///    * TransactionError on failure to rollback changes for the transaction
     ///# Errors
     ///
-    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
-    ///* 'DatabaseError::TransactionError' - failure to execute transaction related query on database
+    ///* 'DatabaseError::TransactionError' - no transaction is open, or failure to execute
+    ///  transaction related query on database
     ///
     fn rollback_transaction(&mut self) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.take_transaction_conn()?;
         conn.query_drop("ROLLBACK")
             .map_err(|e| DatabaseError::TransactionError(e.to_string()))
     }
+    /// reports whether this is a transaction handle with its transaction open
+    ///
+    ///# Returns
+    ///
+    ///* 'bool' - true until the handle's transaction is committed or rolled back
+    ///
+    fn in_transaction(&self) -> bool {
+        self.transaction_conn.is_some()
+    }
     /// attempt to get all clients from the database.
     ///
     /// # Arguments
@@ -351,10 +701,7 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
     fn get_clients(&self) -> Result<Vec<Client>, DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         let clients = conn
             .query_map(
                 "SELECT client_id, client_name, client_service, assigned_employee FROM clients",
@@ -365,6 +712,37 @@ impl DatabaseManager for MySqlDatabase {
             .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
         Ok(clients)
     }
+    /// attempt to get a single client from the database.
+    ///
+    /// within a transaction the row stays locked until the transaction
+    /// ends (FOR UPDATE), so it cannot change between this read and an update.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - reference to (self) MySql database instance
+    /// * `client_id` - id of the client to get
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<Client>, DatabaseError>' - the client, None when no client has the id
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute query on the database
+    ///
+    fn get_client(&self, client_id: i32) -> Result<Option<Client>, DatabaseError> {
+        let mut conn = self.conn()?;
+        let result: Option<(i32, String, i32, i32)> = conn
+            .exec_first(
+                "SELECT client_id, client_name, client_service, assigned_employee FROM clients WHERE client_id = :id FOR UPDATE",
+                params! {"id" => client_id},
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        Ok(result.map(|(client_id, client_name, client_service, assigned_employee)| {
+            Client::new(client_id, client_name, client_service, assigned_employee)
+        }))
+    }
     /// attempt to save new client to database.
     ///
     /// # Arguments
@@ -374,26 +752,22 @@ impl DatabaseManager for MySqlDatabase {
     ///
     ///# Returns
     ///
-    ///* 'Result<(), DatabaseError> ' -
-    ///     on success:
-    ///         Ok(()) status update when client successfully inserted
-    ///     on fail:
-    ///         ConnectionError when the database connection cannot be established
-    ///         QueryError on failure to successfully process this insert into query for clients table
+    ///* 'Result<i32, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(i32) the auto increment client_id assigned to the inserted client
+    ///  * on fail:
+    ///    * ConnectionError when the database connection cannot be established
+    ///    * QueryError on failure to successfully process this insert into query for clients table
     ///
     ///# Errors
     ///
     ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
-
     /// return `DatabaseError` if client fails to save
     // implement inserting new client row into database
-    fn new_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+    fn new_client(&mut self, client: &Client) -> Result<i32, DatabaseError> {
+        let mut conn = self.conn()?;
         conn.exec_drop(
             "INSERT INTO clients (client_name, client_service, assigned_employee) VALUES (?, ?, ?)",
             (
@@ -402,7 +776,9 @@ impl DatabaseManager for MySqlDatabase {
                 client.get_asn_employee(),
             ),
         )
-        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        // last_insert_id is per connection, so it must be read on the same conn
+        generated_id(conn.last_insert_id())
     }
     /// attempt to update client instance in database
     ///
@@ -427,10 +803,7 @@ impl DatabaseManager for MySqlDatabase {
     /// return `DatabaseError` if client update fails
     // implement updating a client row (service / employee partner) in db
     fn update_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         conn.exec_drop(
             "UPDATE clients SET client_name = ?, client_service = ?, assigned_employee = ? WHERE client_id = ?",
             (client.get_client_name(), client.get_client_service(), client.get_asn_employee(), client.get_client_id()),
@@ -459,10 +832,7 @@ impl DatabaseManager for MySqlDatabase {
     /// return `DatabaseError` if client removal fails
     // implement function to remove a client row from the db
     fn remove_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         conn.exec_drop(
             "DELETE FROM clients WHERE client_id = ?",
             (client.get_client_id(),),
@@ -493,19 +863,14 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
     fn get_employee(&self, employee_id: i32) -> Result<Option<Employee>, DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
-        let result: Option<(i32, String, String)> = conn.exec_first(
-            "SELECT employee_id, employee_name, hashed_password FROM employees WHERE employee_id = :id",
+        let mut conn = self.conn()?;
+        let result: Option<(i32, String, String, i32)> = conn.exec_first(
+            "SELECT employee_id, employee_name, hashed_password, employee_role FROM employees WHERE employee_id = :id",
             params! {"id" => employee_id}
         ).map_err(|e| DatabaseError::QueryError(e.to_string()))?;
 
         match result {
-            Some((id, name, hash)) => Employee::new(id, &name, &hash)
-                .map(Some)
-                .map_err(|e| DatabaseError::QueryError(format!("Error creating employee: {}", e))),
+            Some((id, name, hash, role)) => stored_employee(id, &name, &hash, role).map(Some),
             None => Ok(None),
         }
     }
@@ -532,27 +897,20 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
     fn get_employees(&self) -> Result<Vec<Employee>, DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
-        let employee_data: Vec<(i32, String, String)> = conn
+        let mut conn = self.conn()?;
+        let employee_data: Vec<(i32, String, String, i32)> = conn
             .query_map(
-                "SELECT employee_id, employee_name, hashed_password FROM employees",
-                |(employee_id, employee_name, hashed_password)| {
-                    (employee_id, employee_name, hashed_password)
+                "SELECT employee_id, employee_name, hashed_password, employee_role FROM employees",
+                |(employee_id, employee_name, hashed_password, employee_role)| {
+                    (employee_id, employee_name, hashed_password, employee_role)
                 },
             )
             .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
 
-        let mut employees = Vec::new();
-        for (id, name, hash) in employee_data {
-            let employee = Employee::new(id, &name, &hash).map_err(|e| {
-                DatabaseError::QueryError(format!("Error creating employee: {}", e))
-            })?;
-            employees.push(employee);
-        }
-        Ok(employees)
+        employee_data
+            .into_iter()
+            .map(|(id, name, hash, role)| stored_employee(id, &name, &hash, role))
+            .collect()
     }
 
     /// attempt to retrieve employee pass_hash from database
@@ -582,10 +940,7 @@ impl DatabaseManager for MySqlDatabase {
     // query database for the employee with ID
     // return their password hash if found, or None if not found
     fn get_employee_hash(&mut self, employee_id: i32) -> Result<Option<String>, DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         let result: Option<String> = conn
             .exec_first(
                 "SELECT hashed_password FROM employees WHERE employee_id = :id",
@@ -605,12 +960,12 @@ impl DatabaseManager for MySqlDatabase {
     ///
     ///# Returns
     ///
-    ///* 'Result<(), DatabaseError> ' -
-    ///     on success:
-    ///         Ok(()) status update when employee successfully inserted
-    ///     on fail:
-    ///         ConnectionError when the database connection cannot be established
-    ///         QueryError on failure to successfully process this insert into query for employee table
+    ///* 'Result<i32, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(i32) the auto increment employee_id assigned to the inserted employee
+    ///  * on fail:
+    ///    * ConnectionError when the database connection cannot be established
+    ///    * QueryError on failure to successfully process this insert into query for employee table
     ///
     /// # Errors
     ///
@@ -618,19 +973,18 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
     /// return `DatabaseError` if employee fails to save
-    fn new_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+    fn new_employee(&mut self, employee: &Employee) -> Result<i32, DatabaseError> {
+        let mut conn = self.conn()?;
         conn.exec_drop(
-            "INSERT INTO employees (employee_name, hashed_password) VALUES (:name, :password)",
+            "INSERT INTO employees (employee_name, hashed_password, employee_role) VALUES (:name, :password, :role)",
             params! {
             "name" => employee.get_employee_name(),
             "password" => employee.get_employee_hash(),
+            "role" => employee.get_employee_role() as i32,
             },
         )
-        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        generated_id(conn.last_insert_id())
     }
     /// attempt to update employee instance in database
     ///
@@ -655,15 +1009,13 @@ impl DatabaseManager for MySqlDatabase {
     ///
     /// return `DatabaseError` if employee fails to update in db
     fn update_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         conn.exec_drop(
-            "UPDATE employees SET employee_name = :name, hashed_password = :password WHERE employee_id = :id",
+            "UPDATE employees SET employee_name = :name, hashed_password = :password, employee_role = :role WHERE employee_id = :id",
            params! {
             "name" => employee.get_employee_name(),
             "password" => employee.get_employee_hash(),
+            "role" => employee.get_employee_role() as i32,
             "id" => employee.get_employee_id(),
             },
         ).map_err(|e| DatabaseError::QueryError(e.to_string()))
@@ -690,10 +1042,7 @@ impl DatabaseManager for MySqlDatabase {
     ///* 'DatabaseError::QueryError' - failure to execute query on the database
     ///
     fn remove_employee(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
-        let mut conn = self
-            .pool
-            .get_conn()
-            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+        let mut conn = self.conn()?;
         conn.exec_drop(
             "DELETE FROM employees WHERE employee_id = :id",
             params! {
@@ -702,4 +1051,1377 @@ impl DatabaseManager for MySqlDatabase {
         )
         .map_err(|e| DatabaseError::QueryError(e.to_string()))
     }
+    /// attempt to save an audit log entry in database
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `entry` - AuditEntry to insert into the audit_log table
+    ///
+    /// # Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute query on the database
+    ///
+    fn new_audit_entry(&mut self, entry: &AuditEntry) -> Result<(), DatabaseError> {
+        let mut conn = self.conn()?;
+        conn.exec_drop(
+            "INSERT INTO audit_log (occurred_at, employee_id, action, target_id, before_value, after_value) \
+            VALUES (:occurred_at, :employee_id, :action, :target_id, :before_value, :after_value)",
+            params! {
+            "occurred_at" => entry.occurred_at,
+            "employee_id" => entry.employee_id,
+            "action" => entry.action.name(),
+            "target_id" => entry.target_id,
+            "before_value" => entry.before.as_ref(),
+            "after_value" => entry.after.as_ref(),
+            },
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+    /// attempt to save a client history entry in database
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `entry` - ClientHistoryEntry to insert into the client_history table
+    ///
+    /// # Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute query on the database
+    ///
+    fn new_history_entry(&mut self, entry: &ClientHistoryEntry) -> Result<(), DatabaseError> {
+        let mut conn = self.conn()?;
+        conn.exec_drop(
+            "INSERT INTO client_history (occurred_at, client_id, changed_by, action, old_value, new_value) \
+            VALUES (:occurred_at, :client_id, :changed_by, :action, :old_value, :new_value)",
+            params! {
+            "occurred_at" => entry.occurred_at,
+            "client_id" => entry.client_id,
+            "changed_by" => entry.changed_by,
+            "action" => entry.action as i32,
+            "old_value" => entry.old_value,
+            "new_value" => entry.new_value,
+            },
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+    /// attempt to search the client history
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - reference to (self) MySql database instance
+    /// * `filter` - the search, unset fields match any entry
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Vec<ClientHistoryEntry>, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(Vec<ClientHistoryEntry>) - matching entries, oldest first
+    ///  * on fail:
+    ///    * ConnectionError when the database connection cannot be established
+    ///    * QueryError on failure to successfully process this select query
+    ///
+    fn get_client_history(
+        &self,
+        filter: &HistoryFilter,
+    ) -> Result<Vec<ClientHistoryEntry>, DatabaseError> {
+        let (query, values) = history_query(filter);
+        let mut conn = self.conn()?;
+        let rows: Vec<(i64, i32, i32, i32, i32, i32)> = conn
+            .exec(query, values)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rows.into_iter().map(stored_history_entry).collect()
+    }
+    /// attempt to get an employee's failed login record
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - reference to (self) MySql database instance
+    /// * `employee_id` - id of the employee
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<LoginAttempts>, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(Some(LoginAttempts)) - the employee's failed login record
+    ///    * Ok(None) - the employee has no failed attempts recorded
+    ///  * on fail:
+    ///    * ConnectionError when the database connection cannot be established
+    ///    * QueryError on failure to successfully process this select query
+    ///
+    fn get_login_attempts(&self, employee_id: i32) -> Result<Option<LoginAttempts>, DatabaseError> {
+        let mut conn = self.conn()?;
+        let result: Option<(i32, i64, i64)> = conn
+            .exec_first(
+                "SELECT failed_attempts, last_failed_at, locked_until FROM login_attempts \
+                WHERE employee_id = :id",
+                params! {"id" => employee_id},
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        Ok(result.map(|(failed_attempts, last_failed_at, locked_until)| LoginAttempts {
+            employee_id,
+            failed_attempts,
+            last_failed_at,
+            locked_until,
+        }))
+    }
+    /// attempt to add a failed attempt to an employee's failed login record
+    ///
+    /// inserts the record with one failed attempt, or increments the
+    /// existing one's in the query (failed_attempts + 1), so that concurrent
+    /// failures are never lost. The lockout is then set from the new count.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `employee_id` - id of the employee the login failed for
+    /// * `now` - unix timestamp of the failed attempt
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<LoginAttempts, DatabaseError>' - the employee's updated failed login record
+    ///
+    /// # Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute query on the database
+    ///
+    fn record_login_failure(
+        &mut self,
+        employee_id: i32,
+        now: i64,
+    ) -> Result<LoginAttempts, DatabaseError> {
+        let mut conn = self.conn()?;
+        conn.exec_drop(
+            "INSERT INTO login_attempts (employee_id, failed_attempts, last_failed_at, locked_until) \
+            VALUES (:id, 1, :now, 0) \
+            ON DUPLICATE KEY UPDATE failed_attempts = failed_attempts + 1, last_failed_at = VALUES(last_failed_at)",
+            params! {"id" => employee_id, "now" => now},
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let row: Option<(i32, i64)> = conn
+            .exec_first(
+                "SELECT failed_attempts, locked_until FROM login_attempts WHERE employee_id = :id",
+                params! {"id" => employee_id},
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let (failed_attempts, locked_until) = row.ok_or_else(|| {
+            DatabaseError::NotFoundError(format!(
+                "No failed login record for Employee: {}",
+                employee_id
+            ))
+        })?;
+        let mut attempts = LoginAttempts {
+            employee_id,
+            failed_attempts,
+            last_failed_at: now,
+            locked_until,
+        };
+        attempts.apply_lockout(now);
+        conn.exec_drop(
+            "UPDATE login_attempts SET locked_until = :locked_until WHERE employee_id = :id",
+            params! {"locked_until" => attempts.locked_until, "id" => employee_id},
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        Ok(attempts)
+    }
+    /// attempt to remove an employee's failed login record
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `employee_id` - id of the employee
+    ///
+    /// # Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute query on the database
+    ///
+    fn clear_login_attempts(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
+        let mut conn = self.conn()?;
+        conn.exec_drop(
+            "DELETE FROM login_attempts WHERE employee_id = :id",
+            params! {"id" => employee_id},
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to retrieve the applied schema migration versions
+    ///
+    /// creates the schema_migrations metadata table when it does not
+    /// exist yet, then returns the versions recorded in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Vec<i32>, DatabaseError> ' -
+    ///  * on success:
+    ///    * Ok(Vec<i32>) - applied migration versions, in version order
+    ///  * on fail:
+    ///    * ConnectionError when the database connection cannot be established
+    ///    * QueryError on failure to create or read the schema_migrations table
+    ///
+    fn applied_migrations(&mut self) -> Result<Vec<i32>, DatabaseError> {
+        let mut conn = self.conn()?;
+        conn.query_drop(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INT NOT NULL PRIMARY KEY,
+                name VARCHAR(255) NOT NULL,
+                applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        conn.query("SELECT version FROM schema_migrations ORDER BY version")
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+    /// attempt to apply & record a schema migration
+    ///
+    /// runs each of the migration's MySQL statements, then records its
+    /// version in schema_migrations. MySQL commits DDL statements
+    /// implicitly, so a failed migration is reported, not rolled back.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to MySql database instance
+    /// * `migration` - the Migration to apply
+    ///
+    /// # Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to establish connection to the database
+    ///* 'DatabaseError::QueryError' - failure to execute a migration statement
+    ///
+    fn apply_migration(&mut self, migration: &Migration) -> Result<(), DatabaseError> {
+        let mut conn = self.conn()?;
+        for statement in migration.mysql {
+            conn.query_drop(statement).map_err(|e| {
+                DatabaseError::QueryError(format!(
+                    "Migration {} ({}) failed: {}",
+                    migration.version, migration.name, e
+                ))
+            })?;
+        }
+        conn.exec_drop(
+            "INSERT INTO schema_migrations (version, name) VALUES (:version, :name)",
+            params! {
+            "version" => migration.version,
+            "name" => migration.name,
+            },
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+}
+
+/// Represents the tables held by the in-memory database
+///
+/// Plain copy of the clients, employees, audit_log, client_history and
+/// login_attempts tables. Cloned in
+/// full when a transaction begins, as the transaction handle's working copy.
+///
+///# Fields
+///
+///* `clients: BTreeMap<i32, Client>` - client rows keyed by client_id
+///* `employees: BTreeMap<i32, Employee>` - employee rows keyed by employee_id
+///* `audit_log: Vec<AuditEntry>` - audit log rows, in insertion order
+///* `client_history: Vec<ClientHistoryEntry>` - client history rows, in insertion order
+///* `login_attempts: BTreeMap<i32, LoginAttempts>` - failed login rows keyed by employee_id
+///
+#[derive(Clone, Debug, Default)]
+struct InMemoryTables {
+    clients: BTreeMap<i32, Client>,
+    employees: BTreeMap<i32, Employee>,
+    audit_log: Vec<AuditEntry>,
+    client_history: Vec<ClientHistoryEntry>,
+    login_attempts: BTreeMap<i32, LoginAttempts>,
+}
+
+/// Represents the shared state behind every InMemoryDatabase handle
+///
+///# Fields
+///
+///* `tables: InMemoryTables` - the committed table data
+///* `transaction_open: bool` - true while a transaction handle (begin_transaction)
+///  has its transaction open
+///* `next_client_id: i32` - next auto increment value for clients
+///* `next_employee_id: i32` - next auto increment value for employees
+///* `migrations: Vec<i32>` - applied schema migration versions, the in-memory
+///  tables have no schema, so migrations are only recorded
+///
+// auto increment counters live outside of the tables, like InnoDB, a
+// rolled back insert still uses up its id value
+#[derive(Debug)]
+struct InMemoryState {
+    tables: InMemoryTables,
+    transaction_open: bool,
+    next_client_id: i32,
+    next_employee_id: i32,
+    migrations: Vec<i32>,
+}
+
+/// Represents a database that is kept entirely in process memory
+///
+/// Alternative DatabaseManager implementation that does not need a
+/// MySQL server, used for offline use of the application and for tests.
+/// Cloned handles share the same state, the same way clones of the
+/// MySqlDatabase share one connection pool.
+///
+/// A transaction handle works on its own copy of the tables, which
+/// replaces the shared tables on commit. Other handles keep reading the
+/// committed tables, so they never see uncommitted changes (read
+/// committed). Only one transaction may be open at a time, and while it
+/// is open, writes through other handles fail with TransactionError, so
+/// the commit cannot overwrite them.
+///
+///# Fields
+///
+///* `state: Arc<Mutex<InMemoryState>>` - shared table data & auto increment counters
+///* `working: Option<InMemoryTables>` - the transaction's copy of the tables,
+///  Some for a transaction handle (begin_transaction) whose transaction is still open
+///
+#[derive(Debug)]
+pub struct InMemoryDatabase {
+    state: Arc<Mutex<InMemoryState>>,
+    working: Option<InMemoryTables>,
+}
+
+impl InMemoryDatabase {
+    /// Constructor function for an empty InMemoryDatabase
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - new, empty InMemoryDatabase instance
+    ///
+    pub fn new() -> Self {
+        InMemoryDatabase {
+            state: Arc::new(Mutex::new(InMemoryState {
+                tables: InMemoryTables::default(),
+                transaction_open: false,
+                next_client_id: 1,
+                next_employee_id: 1,
+                migrations: Vec::new(),
+            })),
+            working: None,
+        }
+    }
+
+    // lock helper, a poisoned lock is reported like a lost connection
+    fn state(&self) -> Result<MutexGuard<'_, InMemoryState>, DatabaseError> {
+        self.state
+            .lock()
+            .map_err(|e| DatabaseError::ConnectionError(e.to_string()))
+    }
+
+    // reads the tables this handle sees, its working copy during a transaction,
+    // otherwise the committed tables
+    fn read<R, F>(&self, query: F) -> Result<R, DatabaseError>
+    where
+        F: FnOnce(&InMemoryTables) -> R,
+    {
+        match self.working {
+            Some(ref working) => Ok(query(working)),
+            None => Ok(query(&self.state()?.tables)),
+        }
+    }
+
+    // writes the tables this handle sees. The committed tables are only written
+    // while no transaction is open, the lock is held throughout, as a row lock would be
+    fn write<R, F>(&mut self, query: F) -> Result<R, DatabaseError>
+    where
+        F: FnOnce(&mut InMemoryTables) -> R,
+    {
+        if let Some(ref mut working) = self.working {
+            return Ok(query(working));
+        }
+        let mut state = self.state()?;
+        if state.transaction_open {
+            return Err(DatabaseError::TransactionError(
+                "A transaction is in progress on another handle".to_string(),
+            ));
+        }
+        Ok(query(&mut state.tables))
+    }
+
+    // takes the next value of an auto increment counter, checking first that
+    // this handle may write
+    fn next_id<F>(&self, counter: F) -> Result<i32, DatabaseError>
+    where
+        F: FnOnce(&mut InMemoryState) -> &mut i32,
+    {
+        let mut state = self.state()?;
+        if self.working.is_none() && state.transaction_open {
+            return Err(DatabaseError::TransactionError(
+                "A transaction is in progress on another handle".to_string(),
+            ));
+        }
+        let counter = counter(&mut state);
+        let id = *counter;
+        *counter += 1;
+        Ok(id)
+    }
+
+    // ends the handle's transaction, returning its working copy of the tables
+    fn take_working(&mut self) -> Result<InMemoryTables, DatabaseError> {
+        let working = self.working.take().ok_or_else(|| {
+            DatabaseError::TransactionError("No transaction is in progress".to_string())
+        })?;
+        self.state()?.transaction_open = false;
+        Ok(working)
+    }
+}
+
+impl Clone for InMemoryDatabase {
+    /// Clones the InMemoryDatabase, sharing the same state
+    ///
+    /// the clone never inherits this handle's open transaction.
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - new InMemoryDatabase handle, no open transaction
+    ///
+    fn clone(&self) -> Self {
+        InMemoryDatabase {
+            state: Arc::clone(&self.state),
+            working: None,
+        }
+    }
+}
+
+impl Drop for InMemoryDatabase {
+    /// Rolls back a transaction handle dropped while still open
+    fn drop(&mut self) {
+        if self.working.is_some() {
+            let _ = self.rollback_transaction();
+        }
+    }
+}
+
+impl DatabaseManager for InMemoryDatabase {
+    /// Creates a new boxed clone of the implemented DatabaseManager
+    ///
+    /// The clone shares the same underlying state as this instance.
+    ///
+    ///# Returns
+    ///
+    ///* 'Box<dyn DatabaseManager>' - a new/cloned, boxed DatabaseManager
+    ///
+    fn clone_box(&self) -> Box<dyn DatabaseManager> {
+        Box::new(self.clone())
+    }
+
+    /// initiates / begins new in-memory transaction
+    ///
+    /// Copies the committed tables as the transaction handle's working copy.
+    /// Only one transaction may be open on the shared state at a time.
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Box<dyn DatabaseManager>, DatabaseError>' - the transaction handle
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - the shared state lock was poisoned
+    ///* 'DatabaseError::TransactionError' - a transaction is already open
+    ///
+    fn begin_transaction(&self) -> Result<Box<dyn DatabaseManager>, DatabaseError> {
+        let mut state = self.state()?;
+        if state.transaction_open {
+            return Err(DatabaseError::TransactionError(
+                "A transaction is already in progress".to_string(),
+            ));
+        }
+        state.transaction_open = true;
+        Ok(Box::new(InMemoryDatabase {
+            state: Arc::clone(&self.state),
+            working: Some(state.tables.clone()),
+        }))
+    }
+
+    /// commits the handle's in-memory transaction, its working copy becomes the committed tables
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - the shared state lock was poisoned
+    ///* 'DatabaseError::TransactionError' - this handle has no open transaction
+    ///
+    fn commit_transaction(&mut self) -> Result<(), DatabaseError> {
+        let working = self.take_working()?;
+        self.state()?.tables = working;
+        Ok(())
+    }
+
+    /// rolls back the handle's in-memory transaction, discarding its working copy
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - the shared state lock was poisoned
+    ///* 'DatabaseError::TransactionError' - this handle has no open transaction
+    ///
+    fn rollback_transaction(&mut self) -> Result<(), DatabaseError> {
+        self.take_working().map(|_| ())
+    }
+
+    /// reports whether this is a transaction handle with its transaction open
+    fn in_transaction(&self) -> bool {
+        self.working.is_some()
+    }
+
+    /// get all clients, ordered by client_id
+    fn get_clients(&self) -> Result<Vec<Client>, DatabaseError> {
+        self.read(|tables| tables.clients.values().cloned().collect())
+    }
+
+    /// get a single client by client_id
+    fn get_client(&self, client_id: i32) -> Result<Option<Client>, DatabaseError> {
+        self.read(|tables| tables.clients.get(&client_id).cloned())
+    }
+
+    /// save new client, assigning it the next auto increment client_id
+    fn new_client(&mut self, client: &Client) -> Result<i32, DatabaseError> {
+        let client_id = self.next_id(|state| &mut state.next_client_id)?;
+        let mut stored = client.clone();
+        stored.set_client_id(client_id);
+        self.write(|tables| tables.clients.insert(client_id, stored))?;
+        Ok(client_id)
+    }
+
+    /// update client row, matching on client_id
+    fn update_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
+        // same as an UPDATE matching zero rows, a missing client is not an error
+        self.write(|tables| {
+            if let Some(stored) = tables.clients.get_mut(&client.get_client_id()) {
+                *stored = client.clone();
+            }
+        })
+    }
+
+    /// remove client row, matching on client_id
+    fn remove_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
+        self.write(|tables| tables.clients.remove(&client.get_client_id()))?;
+        Ok(())
+    }
+
+    /// retrieve the stored password hash for an employee
+    fn get_employee_hash(&mut self, employee_id: i32) -> Result<Option<String>, DatabaseError> {
+        self.read(|tables| {
+            tables
+                .employees
+                .get(&employee_id)
+                .map(|employee| employee.get_employee_hash().to_string())
+        })
+    }
+
+    /// save new employee, assigning it the next auto increment employee_id
+    fn new_employee(&mut self, employee: &Employee) -> Result<i32, DatabaseError> {
+        let employee_id = self.next_id(|state| &mut state.next_employee_id)?;
+        let mut stored = employee.clone();
+        stored.set_employee_id(employee_id);
+        self.write(|tables| tables.employees.insert(employee_id, stored))?;
+        Ok(employee_id)
+    }
+
+    /// update employee row, matching on employee_id
+    fn update_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError> {
+        self.write(|tables| {
+            if let Some(stored) = tables.employees.get_mut(&employee.get_employee_id()) {
+                *stored = employee.clone();
+            }
+        })
+    }
+
+    /// remove employee row, matching on employee_id
+    fn remove_employee(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
+        self.write(|tables| tables.employees.remove(&employee_id))?;
+        Ok(())
+    }
+
+    /// get all employees, ordered by employee_id
+    fn get_employees(&self) -> Result<Vec<Employee>, DatabaseError> {
+        self.read(|tables| tables.employees.values().cloned().collect())
+    }
+
+    /// get a single employee by employee_id
+    fn get_employee(&self, employee_id: i32) -> Result<Option<Employee>, DatabaseError> {
+        self.read(|tables| tables.employees.get(&employee_id).cloned())
+    }
+
+    /// append an audit log entry
+    fn new_audit_entry(&mut self, entry: &AuditEntry) -> Result<(), DatabaseError> {
+        self.write(|tables| tables.audit_log.push(entry.clone()))
+    }
+
+    /// append a client history entry
+    fn new_history_entry(&mut self, entry: &ClientHistoryEntry) -> Result<(), DatabaseError> {
+        self.write(|tables| tables.client_history.push(entry.clone()))
+    }
+
+    /// matching client history entries, oldest first
+    fn get_client_history(
+        &self,
+        filter: &HistoryFilter,
+    ) -> Result<Vec<ClientHistoryEntry>, DatabaseError> {
+        let mut entries: Vec<ClientHistoryEntry> = self.read(|tables| {
+            tables
+                .client_history
+                .iter()
+                .filter(|entry| filter.matches(entry))
+                .cloned()
+                .collect()
+        })?;
+        // stable sort, entries with the same timestamp keep insertion order
+        entries.sort_by_key(|entry| entry.occurred_at);
+        Ok(entries)
+    }
+
+    /// failed login row, matching on employee_id
+    fn get_login_attempts(&self, employee_id: i32) -> Result<Option<LoginAttempts>, DatabaseError> {
+        self.read(|tables| tables.login_attempts.get(&employee_id).cloned())
+    }
+
+    /// insert or increment failed login row, matching on employee_id
+    fn record_login_failure(
+        &mut self,
+        employee_id: i32,
+        now: i64,
+    ) -> Result<LoginAttempts, DatabaseError> {
+        self.write(|tables| {
+            let attempts = tables
+                .login_attempts
+                .entry(employee_id)
+                .or_insert_with(|| LoginAttempts::new(employee_id));
+            attempts.record_failure(now);
+            attempts.clone()
+        })
+    }
+
+    /// remove failed login row, matching on employee_id
+    fn clear_login_attempts(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
+        self.write(|tables| tables.login_attempts.remove(&employee_id))?;
+        Ok(())
+    }
+
+    /// get the recorded schema migration versions
+    fn applied_migrations(&mut self) -> Result<Vec<i32>, DatabaseError> {
+        Ok(self.state()?.migrations.clone())
+    }
+
+    /// record a schema migration, there is no schema to change
+    fn apply_migration(&mut self, migration: &Migration) -> Result<(), DatabaseError> {
+        self.state()?.migrations.push(migration.version);
+        Ok(())
+    }
+}
+
+/// Represents the SQLite database connection
+///
+/// single file alternative to the MySqlDatabase, uses the same clients
+/// and employees tables. Every handle opens its own connection to the
+/// file, clones open theirs on first use. Statements of one handle
+/// therefore never run inside another handle's transaction.
+///
+/// A transaction handle begins with BEGIN IMMEDIATE, taking the file's
+/// write lock. Only one transaction may be open at a time, a second
+/// begin_transaction, or a write through another handle, waits up to
+/// SQLITE_BUSY_TIMEOUT for it to end before failing. Other handles
+/// read the committed data meanwhile.
+///
+///# Fields
+///
+///* `path: PathBuf` - path of the database file
+///* `conn: OnceCell<Connection>` - this handle's connection to the file,
+///  opened on first use
+///* `transaction: bool` - true for a transaction handle (begin_transaction) whose
+///  transaction is still open
+///
+#[cfg(feature = "sqlite")]
+#[derive(Debug)]
+pub struct SqliteDatabase {
+    path: PathBuf,
+    conn: OnceCell<Connection>,
+    transaction: bool,
+}
+
+/// how long a sqlite connection waits for another connection's lock
+#[cfg(feature = "sqlite")]
+pub const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[cfg(feature = "sqlite")]
+impl SqliteDatabase {
+    /// Constructor function for the SqliteDatabase implementation
+    ///
+    /// Opens (or creates) the database file. The path is taken from the
+    /// SQLITE_PATH variable, then the `database.sqlite_path` key of
+    /// config.toml, and defaults to ./client_management.db
+    /// The tables are created by the schema migrations (migrations.rs).
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Self, ApplicationError>' - The result of opening the file, either:
+    ///  * 'Ok(SqliteDatabase)' - The open database connection
+    ///  * 'ApplicationError' - An error referencing the cause of the failure
+    ///
+    pub fn new() -> Result<Self, ApplicationError> {
+        let path = match env::var("SQLITE_PATH") {
+            Ok(path) => PathBuf::from(path),
+            Err(_) => PathBuf::from(
+                load_config(false)?
+                    .get_string("database.sqlite_path")
+                    .unwrap_or_else(|_| "./client_management.db".to_string()),
+            ),
+        };
+        debug!("SQLITE PATH: {:?}", path);
+
+        let database = Self::open(path)?;
+        debug!("Sqlite database opened successfully.");
+        Ok(database)
+    }
+
+    // opens the database file at path, with its connection already open
+    fn open(path: PathBuf) -> Result<Self, DatabaseError> {
+        let conn = open_connection(&path)?;
+        Ok(SqliteDatabase {
+            path,
+            conn: OnceCell::from(conn),
+            transaction: false,
+        })
+    }
+
+    // this handle's connection, opened on first use
+    fn conn(&self) -> Result<&Connection, DatabaseError> {
+        if self.conn.get().is_none() {
+            let _ = self.conn.set(open_connection(&self.path)?);
+        }
+        self.conn.get().ok_or_else(|| {
+            DatabaseError::ConnectionError("The sqlite connection could not be opened".to_string())
+        })
+    }
+
+    // ends the handle's transaction with the given statement (COMMIT / ROLLBACK)
+    fn finish_transaction(&mut self, statement: &str) -> Result<(), DatabaseError> {
+        if !self.transaction {
+            return Err(DatabaseError::TransactionError(
+                "No transaction is in progress".to_string(),
+            ));
+        }
+        self.transaction = false;
+        let conn = self.conn()?;
+        conn.execute_batch(statement).map_err(|e| {
+            // do not leave the connection inside a failed transaction
+            if !conn.is_autocommit() {
+                let _ = conn.execute_batch("ROLLBACK");
+            }
+            DatabaseError::TransactionError(e.to_string())
+        })
+    }
+}
+
+// opens a connection to the database file, waiting on other connections' locks
+#[cfg(feature = "sqlite")]
+fn open_connection(path: &PathBuf) -> Result<Connection, DatabaseError> {
+    let conn = Connection::open(path).map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+    conn.busy_timeout(SQLITE_BUSY_TIMEOUT)
+        .map_err(|e| DatabaseError::ConnectionError(e.to_string()))?;
+    Ok(conn)
+}
+
+#[cfg(feature = "sqlite")]
+impl Clone for SqliteDatabase {
+    /// Clones the SqliteDatabase, on the same database file
+    ///
+    /// the clone opens its own connection on first use, so it never
+    /// inherits this handle's open transaction.
+    fn clone(&self) -> Self {
+        SqliteDatabase {
+            path: self.path.clone(),
+            conn: OnceCell::new(),
+            transaction: false,
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Drop for SqliteDatabase {
+    /// Rolls back a transaction handle dropped while still open
+    fn drop(&mut self) {
+        if self.transaction {
+            let _ = self.rollback_transaction();
+        }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl DatabaseManager for SqliteDatabase {
+    /// Creates a new boxed clone, on the same database file
+    fn clone_box(&self) -> Box<dyn DatabaseManager> {
+        Box::new(self.clone())
+    }
+
+    /// initiates / begins new database transaction
+    ///
+    /// opens a connection for the transaction handle, and takes the write
+    /// lock with BEGIN IMMEDIATE, waiting up to SQLITE_BUSY_TIMEOUT for an
+    /// open transaction to end.
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Box<dyn DatabaseManager>, DatabaseError>' - the transaction handle
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to open the database file
+    ///* 'DatabaseError::TransactionError' - failure to execute BEGIN, e.g. another
+    ///  transaction stayed open longer than SQLITE_BUSY_TIMEOUT
+    ///
+    fn begin_transaction(&self) -> Result<Box<dyn DatabaseManager>, DatabaseError> {
+        let conn = open_connection(&self.path)?;
+        conn.execute_batch("BEGIN IMMEDIATE")
+            .map_err(|e| DatabaseError::TransactionError(e.to_string()))?;
+        Ok(Box::new(SqliteDatabase {
+            path: self.path.clone(),
+            conn: OnceCell::from(conn),
+            transaction: true,
+        }))
+    }
+
+    /// attempts to commit the handle's transaction
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to open the database file
+    ///* 'DatabaseError::TransactionError' - this handle has no open transaction, or
+    ///  failure to execute COMMIT
+    ///
+    fn commit_transaction(&mut self) -> Result<(), DatabaseError> {
+        self.finish_transaction("COMMIT")
+    }
+
+    /// attempts to rollback the handle's transaction
+    ///
+    ///# Errors
+    ///
+    ///* 'DatabaseError::ConnectionError' - failure to open the database file
+    ///* 'DatabaseError::TransactionError' - this handle has no open transaction, or
+    ///  failure to execute ROLLBACK
+    ///
+    fn rollback_transaction(&mut self) -> Result<(), DatabaseError> {
+        self.finish_transaction("ROLLBACK")
+    }
+
+    /// reports whether this is a transaction handle with its transaction open
+    fn in_transaction(&self) -> bool {
+        self.transaction
+    }
+
+    /// attempt to get all clients from the database.
+    fn get_clients(&self) -> Result<Vec<Client>, DatabaseError> {
+        let conn = self.conn()?;
+        let mut statement = conn
+            .prepare(
+                "SELECT client_id, client_name, client_service, assigned_employee FROM clients",
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let clients = statement
+            .query_map([], |row| {
+                Ok(Client::new(row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<Client>, _>>())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        Ok(clients)
+    }
+
+    /// attempt to get a single client from the database.
+    fn get_client(&self, client_id: i32) -> Result<Option<Client>, DatabaseError> {
+        self.conn()?
+            .query_row(
+                "SELECT client_id, client_name, client_service, assigned_employee FROM clients WHERE client_id = ?1",
+                (client_id,),
+                |row| Ok(Client::new(row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to save new client to database.
+    fn new_client(&mut self, client: &Client) -> Result<i32, DatabaseError> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO clients (client_name, client_service, assigned_employee) VALUES (?1, ?2, ?3)",
+            (
+                client.get_client_name(),
+                client.get_client_service(),
+                client.get_asn_employee(),
+            ),
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        generated_id(conn.last_insert_rowid())
+    }
+
+    /// attempt to update client instance in database
+    fn update_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "UPDATE clients SET client_name = ?1, client_service = ?2, assigned_employee = ?3 WHERE client_id = ?4",
+                (
+                    client.get_client_name(),
+                    client.get_client_service(),
+                    client.get_asn_employee(),
+                    client.get_client_id(),
+                ),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to remove client instance from database
+    fn remove_client(&mut self, client: &Client) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "DELETE FROM clients WHERE client_id = ?1",
+                (client.get_client_id(),),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to retrieve employee pass_hash from database
+    fn get_employee_hash(&mut self, employee_id: i32) -> Result<Option<String>, DatabaseError> {
+        self.conn()?
+            .query_row(
+                "SELECT hashed_password FROM employees WHERE employee_id = ?1",
+                (employee_id,),
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to save employee instance in database
+    fn new_employee(&mut self, employee: &Employee) -> Result<i32, DatabaseError> {
+        let conn = self.conn()?;
+        conn.execute(
+            "INSERT INTO employees (employee_name, hashed_password, employee_role) VALUES (?1, ?2, ?3)",
+            (
+                employee.get_employee_name(),
+                employee.get_employee_hash(),
+                employee.get_employee_role() as i32,
+            ),
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        generated_id(conn.last_insert_rowid())
+    }
+
+    /// attempt to update employee instance in database
+    fn update_employee(&mut self, employee: &Employee) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "UPDATE employees SET employee_name = ?1, hashed_password = ?2, employee_role = ?3 WHERE employee_id = ?4",
+                (
+                    employee.get_employee_name(),
+                    employee.get_employee_hash(),
+                    employee.get_employee_role() as i32,
+                    employee.get_employee_id(),
+                ),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to remove employee instance from database
+    fn remove_employee(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "DELETE FROM employees WHERE employee_id = ?1",
+                (employee_id,),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to get all employees from the database.
+    fn get_employees(&self) -> Result<Vec<Employee>, DatabaseError> {
+        let conn = self.conn()?;
+        let mut statement = conn
+            .prepare(
+                "SELECT employee_id, employee_name, hashed_password, employee_role FROM employees",
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let employee_data: Vec<(i32, String, String, i32)> = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .and_then(|rows| rows.collect())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        employee_data
+            .into_iter()
+            .map(|(id, name, hash, role)| stored_employee(id, &name, &hash, role))
+            .collect()
+    }
+
+    /// attempt to get an employee from the database.
+    fn get_employee(&self, employee_id: i32) -> Result<Option<Employee>, DatabaseError> {
+        let result: Option<(i32, String, String, i32)> = self
+            .conn()?
+            .query_row(
+                "SELECT employee_id, employee_name, hashed_password, employee_role FROM employees WHERE employee_id = ?1",
+                (employee_id,),
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+
+        match result {
+            Some((id, name, hash, role)) => stored_employee(id, &name, &hash, role).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// attempt to save an audit log entry in database
+    fn new_audit_entry(&mut self, entry: &AuditEntry) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "INSERT INTO audit_log (occurred_at, employee_id, action, target_id, before_value, after_value) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                (
+                    entry.occurred_at,
+                    entry.employee_id,
+                    entry.action.name(),
+                    entry.target_id,
+                    entry.before.as_ref(),
+                    entry.after.as_ref(),
+                ),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to save a client history entry in database
+    fn new_history_entry(&mut self, entry: &ClientHistoryEntry) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute(
+                "INSERT INTO client_history (occurred_at, client_id, changed_by, action, old_value, new_value) \
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                (
+                    entry.occurred_at,
+                    entry.client_id,
+                    entry.changed_by,
+                    entry.action as i32,
+                    entry.old_value,
+                    entry.new_value,
+                ),
+            )
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to search the client history
+    fn get_client_history(
+        &self,
+        filter: &HistoryFilter,
+    ) -> Result<Vec<ClientHistoryEntry>, DatabaseError> {
+        let (query, values) = history_query(filter);
+        let conn = self.conn()?;
+        let mut statement = conn
+            .prepare(&query)
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let rows: Vec<(i64, i32, i32, i32, i32, i32)> = statement
+            .query_map(rusqlite::params_from_iter(values), |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?, row.get(5)?))
+            })
+            .and_then(|rows| rows.collect())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        rows.into_iter().map(stored_history_entry).collect()
+    }
+
+    /// attempt to get an employee's failed login record
+    fn get_login_attempts(&self, employee_id: i32) -> Result<Option<LoginAttempts>, DatabaseError> {
+        self.conn()?
+            .query_row(
+                "SELECT failed_attempts, last_failed_at, locked_until FROM login_attempts \
+                WHERE employee_id = ?1",
+                (employee_id,),
+                |row| {
+                    Ok(LoginAttempts {
+                        employee_id,
+                        failed_attempts: row.get(0)?,
+                        last_failed_at: row.get(1)?,
+                        locked_until: row.get(2)?,
+                    })
+                },
+            )
+            .optional()
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to add a failed attempt to an employee's failed login record,
+    /// incremented in the query (failed_attempts + 1) so concurrent failures are never lost
+    fn record_login_failure(
+        &mut self,
+        employee_id: i32,
+        now: i64,
+    ) -> Result<LoginAttempts, DatabaseError> {
+        let conn = self.conn()?;
+        let (failed_attempts, locked_until): (i32, i64) = conn
+            .query_row(
+                "INSERT INTO login_attempts (employee_id, failed_attempts, last_failed_at, locked_until) \
+                VALUES (?1, 1, ?2, 0) \
+                ON CONFLICT (employee_id) DO UPDATE SET failed_attempts = failed_attempts + 1, \
+                last_failed_at = excluded.last_failed_at \
+                RETURNING failed_attempts, locked_until",
+                (employee_id, now),
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let mut attempts = LoginAttempts {
+            employee_id,
+            failed_attempts,
+            last_failed_at: now,
+            locked_until,
+        };
+        attempts.apply_lockout(now);
+        conn.execute(
+            "UPDATE login_attempts SET locked_until = ?1 WHERE employee_id = ?2",
+            (attempts.locked_until, employee_id),
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        Ok(attempts)
+    }
+
+    /// attempt to remove an employee's failed login record
+    fn clear_login_attempts(&mut self, employee_id: i32) -> Result<(), DatabaseError> {
+        self.conn()?
+            .execute("DELETE FROM login_attempts WHERE employee_id = ?1", (employee_id,))
+            .map(|_| ())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to retrieve the applied schema migration versions
+    ///
+    /// creates the schema_migrations metadata table when it does not
+    /// exist yet, then returns the versions recorded in it.
+    fn applied_migrations(&mut self) -> Result<Vec<i32>, DatabaseError> {
+        let conn = self.conn()?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_migrations (
+                version INTEGER NOT NULL PRIMARY KEY,
+                name VARCHAR(255) NOT NULL,
+                applied_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )",
+        )
+        .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        let mut statement = conn
+            .prepare("SELECT version FROM schema_migrations ORDER BY version")
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        statement
+            .query_map([], |row| row.get(0))
+            .and_then(|rows| rows.collect())
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))
+    }
+
+    /// attempt to apply & record a schema migration
+    ///
+    /// sqlite supports transactional DDL, so the statements and the
+    /// schema_migrations record are applied together, or not at all.
+    fn apply_migration(&mut self, migration: &Migration) -> Result<(), DatabaseError> {
+        let conn = self.conn()?;
+        let transaction = conn
+            .unchecked_transaction()
+            .map_err(|e| DatabaseError::TransactionError(e.to_string()))?;
+        for statement in migration.sqlite {
+            transaction.execute_batch(statement).map_err(|e| {
+                DatabaseError::QueryError(format!(
+                    "Migration {} ({}) failed: {}",
+                    migration.version, migration.name, e
+                ))
+            })?;
+        }
+        transaction
+            .execute(
+                "INSERT INTO schema_migrations (version, name) VALUES (?1, ?2)",
+                (migration.version, migration.name),
+            )
+            .map_err(|e| DatabaseError::QueryError(e.to_string()))?;
+        transaction
+            .commit()
+            .map_err(|e| DatabaseError::TransactionError(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::LOCKOUT_THRESHOLD;
+
+    fn test_client(name: &str, employee_id: i32) -> Client {
+        Client::new(0, name.to_string(), 1, employee_id)
+    }
+
+    fn test_employee(name: &str) -> Employee {
+        Employee::from_stored(0, name, "hash", EmployeeRole::Advisor)
+    }
+
+    #[test]
+    fn in_memory_assigns_ids() {
+        let mut db = InMemoryDatabase::new();
+        assert_eq!(db.new_client(&test_client("Ada", 1)).unwrap(), 1);
+        assert_eq!(db.new_client(&test_client("Grace", 1)).unwrap(), 2);
+        assert_eq!(db.new_employee(&test_employee("Alan")).unwrap(), 1);
+
+        // the stored rows carry the assigned id, not the caller's
+        let ids: Vec<i32> = db
+            .get_clients()
+            .unwrap()
+            .iter()
+            .map(|client| client.get_client_id())
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(db.get_employee(1).unwrap().unwrap().get_employee_name(), "Alan");
+    }
+
+    #[test]
+    fn in_memory_ids_are_not_reused() {
+        let mut db = InMemoryDatabase::new();
+        let client_id = db.new_client(&test_client("Ada", 1)).unwrap();
+        db.remove_client(&Client::new(client_id, "Ada".to_string(), 1, 1))
+            .unwrap();
+        assert_eq!(db.new_client(&test_client("Grace", 1)).unwrap(), 2);
+
+        // like InnoDB, a rolled back insert still uses up its id
+        let mut handle = db.begin_transaction().unwrap();
+        assert_eq!(handle.new_client(&test_client("Edsger", 1)).unwrap(), 3);
+        handle.rollback_transaction().unwrap();
+        assert_eq!(db.new_client(&test_client("Barbara", 1)).unwrap(), 4);
+    }
+
+    #[test]
+    fn in_memory_rollback_restores_snapshot() {
+        let mut db = InMemoryDatabase::new();
+        let client_id = db.new_client(&test_client("Ada", 1)).unwrap();
+        db.new_employee(&test_employee("Alan")).unwrap();
+
+        let mut handle = db.begin_transaction().unwrap();
+        handle
+            .update_client(&Client::new(client_id, "Ada Lovelace".to_string(), 2, 1))
+            .unwrap();
+        handle.new_client(&test_client("Grace", 1)).unwrap();
+        handle.remove_employee(1).unwrap();
+        handle.record_login_failure(1, 0).unwrap();
+        handle.rollback_transaction().unwrap();
+
+        let clients = db.get_clients().unwrap();
+        assert_eq!(clients.len(), 1);
+        assert_eq!(clients[0].get_client_name(), "Ada");
+        assert_eq!(clients[0].get_client_service(), 1);
+        assert!(db.get_employee(1).unwrap().is_some());
+        assert!(db.get_login_attempts(1).unwrap().is_none());
+    }
+
+    #[test]
+    fn in_memory_dropped_handle_rolls_back() {
+        let db = InMemoryDatabase::new();
+        {
+            let mut handle = db.begin_transaction().unwrap();
+            handle.new_client(&test_client("Ada", 1)).unwrap();
+        }
+        assert!(db.get_clients().unwrap().is_empty());
+        assert!(db.begin_transaction().is_ok());
+    }
+
+    #[test]
+    fn in_memory_transaction_is_isolated() {
+        let mut db = InMemoryDatabase::new();
+        db.new_client(&test_client("Ada", 1)).unwrap();
+        let mut handle = db.begin_transaction().unwrap();
+        handle.new_client(&test_client("Grace", 1)).unwrap();
+
+        // other handles read the committed tables, and may not write until the commit
+        assert_eq!(db.get_clients().unwrap().len(), 1);
+        assert!(db.new_client(&test_client("Edsger", 1)).is_err());
+        assert!(db.record_login_failure(1, 0).is_err());
+        handle.commit_transaction().unwrap();
+
+        assert_eq!(db.get_clients().unwrap().len(), 2);
+        db.new_client(&test_client("Edsger", 1)).unwrap();
+        assert_eq!(db.get_clients().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn in_memory_clones_share_state() {
+        let mut db = InMemoryDatabase::new();
+        let clone = db.clone_box();
+        db.new_client(&test_client("Ada", 1)).unwrap();
+        assert_eq!(clone.get_clients().unwrap().len(), 1);
+    }
+
+    // checks a backend's record_login_failure against the lockout rules of LoginAttempts
+    fn check_record_login_failure(db: &mut dyn DatabaseManager) {
+        // a second instance, both record failures without reading the record first
+        let mut other = db.clone_box();
+        for attempt in 1..LOCKOUT_THRESHOLD {
+            let attempts = if attempt % 2 == 0 {
+                other.record_login_failure(1, 100).unwrap()
+            } else {
+                db.record_login_failure(1, 100).unwrap()
+            };
+            assert_eq!(attempts.failed_attempts, attempt);
+            assert!(!attempts.is_locked(100));
+        }
+        let attempts = db.record_login_failure(1, 200).unwrap();
+        assert_eq!(attempts.failed_attempts, LOCKOUT_THRESHOLD);
+        assert_eq!(attempts.last_failed_at, 200);
+        assert!(attempts.is_locked(200));
+        assert_eq!(db.get_login_attempts(1).unwrap(), Some(attempts));
+        // other employees are counted separately
+        assert_eq!(
+            other.record_login_failure(2, 200).unwrap().failed_attempts,
+            1
+        );
+    }
+
+    #[test]
+    fn in_memory_records_login_failures() {
+        check_record_login_failure(&mut InMemoryDatabase::new());
+    }
+
+    // a migrated sqlite database in a new temporary file, every handle opens it again
+    #[cfg(feature = "sqlite")]
+    fn test_sqlite(name: &str) -> SqliteDatabase {
+        let path = env::temp_dir().join(format!("final_project_{}_{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut db = SqliteDatabase::open(path).unwrap();
+        crate::migrations::run_migrations(&mut db).unwrap();
+        db
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_records_login_failures() {
+        let mut db = test_sqlite("login_failures");
+        check_record_login_failure(&mut db);
+        let _ = std::fs::remove_file(&db.path);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_transaction_is_isolated() {
+        let mut db = test_sqlite("isolation");
+        db.new_client(&test_client("Ada", 1)).unwrap();
+        let mut handle = db.begin_transaction().unwrap();
+        handle.new_client(&test_client("Grace", 1)).unwrap();
+
+        // the other handles' statements do not run inside the transaction
+        let clone = db.clone_box();
+        assert!(!clone.in_transaction());
+        assert_eq!(clone.get_clients().unwrap().len(), 1);
+        assert_eq!(db.get_clients().unwrap().len(), 1);
+        handle.rollback_transaction().unwrap();
+        assert_eq!(db.get_clients().unwrap().len(), 1);
+
+        let mut handle = db.begin_transaction().unwrap();
+        handle.new_client(&test_client("Grace", 1)).unwrap();
+        handle.commit_transaction().unwrap();
+        assert_eq!(clone.get_clients().unwrap().len(), 2);
+        let _ = std::fs::remove_file(&db.path);
+    }
 }
@@ -30,11 +30,13 @@ use thiserror::Error;
 ///* `PasswordHashError` - occurs from failure to hash
 ///* `PassValidationError` - occurs from failure to validate pass
 ///* `AuthenticationError` - occurs from failing to authenticate
+///* `PermissionError` - the employee's role does not permit the operation
 ///* `IoError` - when i/o related errors occur
 ///* `ConfigError` - when a configuration error occurs
 ///* `DatabaseError` - represents various database errors
 ///* `NoMatchError` - when a match cannot be found
 ///* `InputError` - occurs from input specific errors
+///* `ExportError` - occurs when data cannot be serialized for export
 #[derive(Error, Debug)]
 pub enum ApplicationError {
     #[error("Hashing of password failed: {0}")]
@@ -43,6 +45,8 @@ pub enum ApplicationError {
     PassValidationError(String),
     #[error("Authentication error occurred: {0}")]
     AuthenticationError(String),
+    #[error("Permission denied: {0}")]
+    PermissionError(String),
     #[error("Io error occurred: {0}")]
     IoError(#[from] std::io::Error),
     #[error("A Configuration error occurred: {0}")]
@@ -53,6 +57,8 @@ pub enum ApplicationError {
     NoMatchError(String),
     #[error("Input error occurred: {0}")]
     InputError(String),
+    #[error("Export error occurred: {0}")]
+    ExportError(String),
     #[error("Error occured with an Env variable: {0}")]
     EnvirnVarError(String),
     #[error("Error occurredb when parsing integer: {0}")]
@@ -181,7 +187,6 @@ impl From<VarError> for ApplicationError {
     ///# Returns
     ///
     ///* 'ApplicationError' - result as ApplicationError
-
     fn from(err: VarError) -> Self {
         ApplicationError::EnvirnVarError(err.to_string())
     }
@@ -200,8 +205,115 @@ impl From<ParseIntError> for ApplicationError {
     ///# Returns
     ///
     ///* 'ApplicationError' - result as ApplicationError
-
     fn from(err: ParseIntError) -> Self {
         ApplicationError::ParseIntError(err.to_string())
     }
 }
+
+impl From<csv::Error> for ApplicationError {
+    /// Converts csv::Error into ApplicationErrors
+    ///
+    /// provides a simple method for converting csv::Errors
+    /// into ApplicationErrors
+    ///
+    ///# Arguments
+    ///
+    ///* 'err: csv::Error' -
+    ///
+    ///# Returns
+    ///
+    ///* 'ApplicationError' - IoError for I/O failures, e.g. writing an export,
+    ///  otherwise InputError for rows that could not be parsed
+    fn from(err: csv::Error) -> Self {
+        if err.is_io_error() {
+            ApplicationError::IoError(err.into())
+        } else {
+            ApplicationError::InputError(err.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for ApplicationError {
+    /// Converts serde_json::Error into ApplicationErrors
+    ///
+    /// failures writing the JSON are I/O errors, any other failure
+    /// means the data could not be serialized
+    ///
+    ///# Arguments
+    ///
+    ///* 'err: serde_json::Error' -
+    ///
+    ///# Returns
+    ///
+    ///* 'ApplicationError' - IoError for I/O failures, otherwise ExportError
+    fn from(err: serde_json::Error) -> Self {
+        if err.is_io() {
+            ApplicationError::IoError(err.into())
+        } else {
+            ApplicationError::ExportError(err.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::io::{self, Write};
+
+    // a writer that always fails, as a full disk would
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk full"))
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn json_write_failures_are_io_errors() {
+        let err = serde_json::to_writer(FailingWriter, &vec![1, 2, 3]).unwrap_err();
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::IoError(_)
+        ));
+    }
+
+    #[test]
+    fn csv_write_failures_are_io_errors() {
+        // a one byte buffer, so the record is written out before it is returned
+        let mut writer = csv::WriterBuilder::new()
+            .buffer_capacity(1)
+            .from_writer(FailingWriter);
+        let err = writer.write_record(["1", "Ada"]).unwrap_err();
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::IoError(_)
+        ));
+    }
+
+    #[test]
+    fn csv_parse_failures_are_input_errors() {
+        let mut reader = csv::Reader::from_reader("client_id\nAda\n".as_bytes());
+        let err = reader.deserialize::<(i32,)>().next().unwrap().unwrap_err();
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::InputError(_)
+        ));
+    }
+
+    #[test]
+    fn json_serialization_failures_are_export_errors() {
+        // JSON object keys must be strings
+        let mut map = BTreeMap::new();
+        map.insert(vec![1], "client");
+        let err = serde_json::to_string(&map).unwrap_err();
+        assert!(matches!(
+            ApplicationError::from(err),
+            ApplicationError::ExportError(_)
+        ));
+    }
+}
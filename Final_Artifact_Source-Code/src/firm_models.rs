@@ -23,19 +23,21 @@ use crate::errors::ApplicationError;
 /// represents an employee within the system
 ///
 /// contains all required information for a firm employee
-/// within the system: id, name, and hashed password
+/// within the system: id, name, hashed password, and role
 ///
 ///# Fields
 ///
 ///* `employee_id` - i32 integr value, unique employee identifier
 ///* `employee_name` - string, name of the employee
 ///* `hashed_password` - string, hashed password that was input
+///* `employee_role` - EmployeeRole, what the employee is permitted to do
 // declare and define employee struct
 #[derive(Clone, Debug, PartialEq)]
 pub struct Employee {
     employee_id: i32, // integer
     employee_name: String,
     hashed_password: String,
+    employee_role: EmployeeRole,
 }
 
 impl Employee {
@@ -49,22 +51,60 @@ impl Employee {
     ///* 'employee_id' - i32 integer value of an employee id
     ///* 'name' - reference to employee name string
     ///* 'password' - reference to string data "password"
+    ///* 'role' - the EmployeeRole of the new employee
     ///
     ///# Returns
     ///
     ///* 'Self' - returns static Employee object
     ///
     ///
-    pub fn new(employee_id: i32, name: &str, password: &str) -> Result<Self, ApplicationError> {
+    pub fn new(
+        employee_id: i32,
+        name: &str,
+        password: &str,
+        role: EmployeeRole,
+    ) -> Result<Self, ApplicationError> {
         let hashed_password = Authenticator::hash_password(password)?;
 
         Ok(Employee {
             employee_id,
             employee_name: name.to_string(),
             hashed_password,
+            employee_role: role,
         })
     }
 
+    /// Rebuilds an Employee struct from its persisted fields
+    ///
+    /// used when loading employees back out of storage. the stored
+    /// hash is kept as is, hashing it again would replace the
+    /// employee's credential with a hash of a hash.
+    ///
+    ///# Arguments
+    ///
+    ///* 'employee_id' - i32 integer value of the stored employee id
+    ///* 'name' - reference to the stored employee name string
+    ///* 'hashed_password' - reference to the stored password hash string
+    ///* 'role' - the stored EmployeeRole
+    ///
+    ///# Returns
+    ///
+    ///* 'Self' - returns the rebuilt Employee object
+    ///
+    pub fn from_stored(
+        employee_id: i32,
+        name: &str,
+        hashed_password: &str,
+        role: EmployeeRole,
+    ) -> Self {
+        Employee {
+            employee_id,
+            employee_name: name.to_string(),
+            hashed_password: hashed_password.to_string(),
+            employee_role: role,
+        }
+    }
+
     // accessor method to return employee id value
     ///
     /// returns i32 integer value for the employee_id
@@ -113,14 +153,167 @@ impl Employee {
     pub fn get_employee_hash(&self) -> &str {
         &self.hashed_password
     }
+    /// accessor method to return the employee's role
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - a reference to self
+    ///
+    ///# Returns
+    ///
+    ///* 'EmployeeRole' - the role of the employee (self.employee_role)
+    ///
+    pub fn get_employee_role(&self) -> EmployeeRole {
+        self.employee_role
+    }
+    /// mutator / set method for employee_id
+    ///
+    /// sets local id value for an employee to value provided from db.
+    /// ids are auto generated by db on creation of instance & addition to the database.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - a mutable reference to self
+    ///* 'id' - a 32-bit integer value used to set self.employee_id
+    ///
+    pub fn set_employee_id(&mut self, id: i32) {
+        self.employee_id = id;
+    }
+    /// mutator / set method for employee_name
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - a mutable reference to self
+    ///* 'name' - reference to the new employee name string
+    ///
+    pub fn set_employee_name(&mut self, name: &str) {
+        self.employee_name = name.to_string();
+    }
+    /// mutator / set method for the employee password
+    ///
+    /// hashes the provided plain text password, and replaces the
+    /// stored hashed_password with the result.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - a mutable reference to self
+    ///* 'password' - reference to the new plain text password string
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok, or PasswordHashError when hashing fails
+    ///
+    pub fn change_password(&mut self, password: &str) -> Result<(), ApplicationError> {
+        self.hashed_password = Authenticator::hash_password(password)?;
+        Ok(())
+    }
+    /// mutator / set method for employee_role
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - a mutable reference to self
+    ///* 'role' - the new EmployeeRole of the employee
+    ///
+    pub fn set_employee_role(&mut self, role: EmployeeRole) {
+        self.employee_role = role;
+    }
+}
+
+/// represents the role of an employee within the firm
+///
+/// roles decide which client and employee operations an employee
+/// may perform. They are stored as integers in the employees table.
+///
+///# Variants
+///
+///* `Advisor` - may only modify the clients assigned to them (1)
+///* `Supervisor` - may modify any client (2)
+///* `Admin` - may modify any client, and manage employees (3)
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmployeeRole {
+    Advisor = 1,
+    Supervisor = 2,
+    Admin = 3,
+}
+
+impl EmployeeRole {
+    /// Converts a stored / input integer value to enum variant.
+    ///
+    ///# Arguments
+    ///
+    ///* 'value' - i32 integer value of the role
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(EmployeeRole)' - for matching explicitly defined enum variant
+    ///* 'None' - returns None when no match to a defined enum variant
+    ///
+    pub fn convert_i32(value: i32) -> Option<Self> {
+        match value {
+            1 => Some(Self::Advisor),
+            2 => Some(Self::Supervisor),
+            3 => Some(Self::Admin),
+            _ => None,
+        }
+    }
+
+    /// Converts a role name, or it's number, to enum variant.
+    ///
+    /// Accepts "advisor" / "supervisor" / "admin" (case insensitive),
+    /// or the same integer values accepted by convert_i32.
+    ///
+    ///# Arguments
+    ///
+    ///* 'value' - role name or number
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(EmployeeRole)' - for matching explicitly defined enum variant
+    ///* 'None' - returns None when no match to a defined enum variant
+    ///
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "advisor" => Some(Self::Advisor),
+            "supervisor" => Some(Self::Supervisor),
+            "admin" => Some(Self::Admin),
+            other => other.parse::<i32>().ok().and_then(Self::convert_i32),
+        }
+    }
+
+    /// returns the display name of the role
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Self::Advisor => "advisor",
+            Self::Supervisor => "supervisor",
+            Self::Admin => "admin",
+        }
+    }
+
+    /// returns true when the role may modify clients assigned to other employees
+    pub fn can_modify_any_client(&self) -> bool {
+        *self != Self::Advisor
+    }
+
+    /// returns true when the role may add, modify, and delete employees
+    pub fn can_manage_employees(&self) -> bool {
+        *self == Self::Admin
+    }
 }
 
 // trait to allow access of id/key from AVL tree
+//
+// the AVL tree is ordered by Key alone, so any Ord type may be used, e.g.
+// a name String, or an (employee_id, client_id) tuple for a composite key.
+// the key is returned by reference, so comparing keys never clones them
 pub trait Identification {
-    fn get_key(&self) -> i32;
+    type Key: Ord + Clone + std::fmt::Debug;
+    fn get_key(&self) -> &Self::Key;
 }
 
 impl Identification for Client {
+    type Key = i32;
+
     /// get / accessor method to retrieve a key value
     ///
     /// key value for the AVL_tree. Uses unique 32-bit integer
@@ -134,10 +327,10 @@ impl Identification for Client {
     ///
     ///# Returns
     ///
-    ///* 'i32' - 32-bit integer value, the "key"/ client_id number
+    ///* '&i32' - reference to the 32-bit integer "key"/ client_id number
     ///
-    fn get_key(&self) -> i32 {
-        self.get_client_id()
+    fn get_key(&self) -> &i32 {
+        &self.client_id
     }
 }
 
@@ -12,81 +12,130 @@
 //! This is the primary / main module for SNHU Investment Firm's
 //! client management system
 //!
-//! This application uses a MySQL remote database implementation, and
-//! provides separate modules to meet the various needs of the firm.
+//! This application stores its data in a MySQL remote database, an
+//! in-memory database, or a SQLite file (with the `sqlite` cargo feature),
+//! selected by the ENVIRONMENT variable or `database.backend` in config.toml.
+//! It provides separate modules to meet the various needs of the firm.
 //!
 //! ## Dependencies
 //!
+//! * audit.rs - Contains the audit trail entries recorded for every client and
+//!   employee change: who made it, the values before and after, and when.
+//!
 //! * auth.rs - Contains authentication and cryptography related functions
-//!     to the application. Including hashing of passwords, and authentication
-//!     checks. Uses the [Argon2](https://docs.rs/argon2/latest/argon2/) crate.
+//!   to the application. Including hashing of passwords, and authentication
+//!   checks. Uses the [Argon2](https://docs.rs/argon2/latest/argon2/) crate.
 //!     
+//! * cli.rs - Contains the non-interactive command line subcommands, such as
+//!   `clients list --employee 5` or `client reassign 12 --to 7`, used to
+//!   script changes without the menu. Uses the [clap](https://docs.rs/clap/latest/clap/) crate.
+//!
+//! * client_books.rs - Contains the import of client books from CSV files in
+//!   the clients_list.csv format, and their export as CSV or JSON, for one
+//!   employee or the whole firm. Uses the [csv](https://docs.rs/csv/latest/csv/)
+//!   and [serde_json](https://docs.rs/serde_json/latest/serde_json/) crates.
+//!
+//! * client_cache.rs - Contains the local client cache, the AVL tree of clients with
+//!   indexes by assigned employee, service & name that are kept in sync on every
+//!   change, the index based client search, and the fuzzy client name search.
+//!
+//! * client_history.rs - Contains the history of client reassignments and
+//!   service changes, its search by client, employee, action & date range,
+//!   and its export as CSV. Uses the [chrono](https://docs.rs/chrono/latest/chrono/) crate.
+//!
 //! * data_structs.rs - Contains data structures used to facilitate
-//!     local operations within the application.
+//!   local operations within the application, the AVL tree and its
+//!   secondary indexes.
 //!
 //! * database.rs - Contains the MySQL database connection & implementation.
-//!     Also provide the DatabaseManager trait / interface, to allow a variety
-//!     of query operations to be performed on the database.
-//!     Uses the [MySQL](https://docs.rs/mysql/latest/mysql/) crate.
+//!   Also provide the DatabaseManager trait / interface, to allow a variety
+//!   of query operations to be performed on the database, and an in-memory
+//!   implementation of it for offline use (ENVIRONMENT=memory) and tests.
+//!   With the `sqlite` cargo feature, also provides a single file SQLite
+//!   implementation (ENVIRONMENT=sqlite, or `database.backend` in config.toml).
+//!   Uses the [MySQL](https://docs.rs/mysql/latest/mysql/) crate.
 //!
 //! * errors.rs - Contains the various custom error definitions that are needed
-//!     to handle the various results from operations within the application.
+//!   to handle the various results from operations within the application.
 //!
 //! * firm_models.rs - Contains the application's core structures, the Employee,
-//!     and Client structs. Also includes the implemented functions that
-//!     define their behaviors within the system.
+//!   and Client structs. Also includes the implemented functions that
+//!   define their behaviors within the system.
 //!
 //! * menu.rs - Contains menu related application logic. Directs the flow of the
-//!     information through application depending on user input.
+//!   information through application depending on user input.
+//!
+//! * migrations.rs - Contains the versioned schema migrations that create and
+//!   upgrade the database tables. Applied on startup, or on their own with
+//!   the `migrate` command line argument.
 //!
 //! * operation_handlers.rs - Manages and controls the flow of data between the
-//!     database and local data structures.
-//!     Includes:
-//!     - ClientHandler struct, with implemented function for managing Client
-//!         related operations.
-//!     - EmployeeHandler struct, with implemented function for managing Employee
-//!         related operations.
-//!     - Transaction struct, with implemented function for ensuring consistency
-//!         of operations between local and remote data.
+//!   database and local data structures.
+//!   Includes:
+//!   - ClientHandler struct, with implemented function for managing Client
+//!     related operations.
+//!   - EmployeeHandler struct, with implemented function for managing Employee
+//!     related operations.
+//!   - Transaction struct, with implemented function for ensuring consistency
+//!     of operations between local and remote data.
 //!
 //! * util.rs - Utility functions used for gathering, validating, and sanitizing
-//!     user input.
+//!   user input.
 //!
 //!
 //!
 extern crate argon2;
+extern crate chrono;
+extern crate clap;
 extern crate config;
+extern crate csv;
 extern crate env_logger;
 extern crate log;
 extern crate mysql;
 extern crate rand;
 extern crate regex_syntax;
+extern crate rpassword;
+extern crate serde;
+extern crate serde_json;
+#[cfg(feature = "sqlite")]
+extern crate rusqlite;
 extern crate thiserror;
 extern crate url;
 
+mod audit;
 mod auth;
+mod cli;
+mod client_books;
+mod client_cache;
+mod client_history;
 mod data_structs;
 mod database;
 mod errors;
 mod firm_models;
 mod menu;
+mod migrations;
 mod operation_handlers;
 mod util;
 
 use database::DatabaseManager;
-use firm_models::Employee;
+use firm_models::{Employee, EmployeeRole};
 
-use crate::auth::login_handler;
-use crate::database::MySqlDatabase;
+use crate::auth::{load_idle_timeout, login_handler, HashPolicy, PasswordPolicy};
+use crate::database::open_database;
 use crate::errors::{ApplicationError, DatabaseError};
+use crate::cli::{run_command, Cli, Command};
 use crate::menu::Menu;
+use crate::migrations::run_migrations;
+use crate::operation_handlers::EmployeeHandler;
+use clap::Parser;
 
 /// This is the main function
 ///
 /// This function initializes the primary dependency for the application.
-/// it calls the initial employee set up function, it proceeds to call the
-/// login function, and if successful, then starts the main menu looping for
-/// the application. Otherwise, the application closes.
+/// it calls the initial employee set up function. When a subcommand was
+/// provided on the command line, it runs only that subcommand. Otherwise
+/// it proceeds to call the login function, and if successful, then starts
+/// the main menu looping for the application. Otherwise, the application closes.
 ///     Passes/injects dependency throughout the application with mutable db.
 ///
 ///# Returns
@@ -101,20 +150,46 @@ use crate::menu::Menu;
 ///
 fn main() -> Result<(), ApplicationError> {
     env_logger::init(); // initialize logging
+    let cli = Cli::parse(); // exits with usage when the arguments are invalid
+
+    // password hashing parameters & requirements, from config.toml, before any password is hashed
+    HashPolicy::load()?.install();
+    PasswordPolicy::load()?.install();
 
     //  : type annotation for mutable db.
     //  Box containing trait object implementation of DatabaseManager
-    //  assigned to a box containing new MySqlDatabase instance
-    let mut db: Box<dyn DatabaseManager> = Box::new(MySqlDatabase::new()?);
+    //  assigned to the backend selected by the ENVIRONMENT variable
+    let mut db: Box<dyn DatabaseManager> = open_database()?;
+
+    // create / upgrade the schema before anything queries it.
+    // "migrate" subcommand only applies the migrations, then exits
+    let applied = run_migrations(&mut *db)?;
+    if let Some(Command::Migrate) = cli.command {
+        println!("Applied {} schema migration(s).", applied.len());
+        return Ok(());
+    }
 
     // call initial database seed method.
     // only generates initial employees when db empty
     initial_employee_setup(&mut *db)?;
 
-    // if login_handler returns true
-    if login_handler(&mut *db)? {
+    // non-interactive subcommand, runs without the login prompt / menu
+    if let Some(ref command) = cli.command {
+        return run_command(&cli, command, db);
+    }
+
+    // a new database has no admin, so no one could add the first employees
+    if !EmployeeHandler::new(db.clone_box())?.has_admin()? {
+        eprintln!(
+            "No admin exists yet. Add one with:\n  \
+            final_project employee add --name <name> --role admin"
+        );
+    }
+
+    // if login_handler returns the signed in employee's session
+    if let Some(session) = login_handler(&mut *db)? {
         // begin program's main menu looping
-        let mut menu = Menu::new(db)?;
+        let mut menu = Menu::new(db, session, load_idle_timeout()?)?;
         menu.run()?;
     } else {
         println!("Login process failed. Goodbye.")
@@ -125,8 +200,9 @@ fn main() -> Result<(), ApplicationError> {
 ///
 /// function will only execute database additions if it detects
 /// that the database table for employees is empty.
-/// If empty, any name + password combinations in the employees vector
-/// will be used to seed the database.
+/// If empty, any name + password + role combinations in the employees vector
+/// will be used to seed the database. Seed at least one Admin, otherwise
+/// no one can manage employees.
 /// If not empty, the function immediately returns with Ok result.
 ///
 ///# Arguments
@@ -146,26 +222,27 @@ fn main() -> Result<(), ApplicationError> {
 fn initial_employee_setup(database: &mut dyn DatabaseManager) -> Result<(), ApplicationError> {
     // check if employee_id 1 exists, if Ok and Some, db not empty, return result Ok(())
     if let Ok(Some(_)) = database.get_employee_hash(1) {
-        println!("Database was previously seeded! Use an existing account.");
+        // stderr, keeps the stdout of subcommands clean for scripts
+        eprintln!("Database was previously seeded! Use an existing account.");
         return Ok(());
     }
     // employees added to this vector will be added to the database if it is currently empty.
     // in the format shown. was used to insert initial test data,
-    let employees = vec![
-    //("name1", "password1"),
-    //("name2", "password2"),
+    let employees: Vec<(&str, &str, EmployeeRole)> = vec![
+    //("name1", "password1", EmployeeRole::Admin),
+    //("name2", "password2", EmployeeRole::Advisor),
     ];
-    // initiates a database transaction
-    database.begin_transaction()?;
+    // initiates a database transaction, the additions are made through its handle
+    let mut transaction = database.begin_transaction()?;
 
     // hondles the addition / db modification with closure
     let result: Result<(), ApplicationError> = (|| {
-        for (e_name, e_password) in employees {
-            // iterates through array,each name/pass pair
-            // maps the name/pass to implemented Employee struct
-            let employee = Employee::new(0, e_name, e_password)?;
+        for (e_name, e_password, e_role) in employees {
+            // iterates through array,each name/pass/role set
+            // maps the name/pass/role to implemented Employee struct
+            let employee = Employee::new(0, e_name, e_password, e_role)?;
             // attempts to add new Employee to db, matches result to one of the 3 outcomes
-            match database.new_employee(&employee) {
+            match transaction.new_employee(&employee) {
                 Ok(_) => println!("Added the employee: {} to database.", e_name),
                 Err(DatabaseError::QueryError(e)) if e.contains("duplicate") => {
                     println!("That employee already exists: {}", e_name);
@@ -179,10 +256,10 @@ fn initial_employee_setup(database: &mut dyn DatabaseManager) -> Result<(), Appl
 
     if result.is_err() {
         // when any step of transaction generates an error, rollsback changes
-        database.rollback_transaction()?;
+        transaction.rollback_transaction()?;
     } else {
         // otherwise commit the changes when done
-        database.commit_transaction()?;
+        transaction.commit_transaction()?;
     }
     result
 }
@@ -7,12 +7,28 @@
 //! This module implements the menu related interface for
 //! managing clients and their service choices. Handles input operations
 
+use std::path::Path;
 use std::result::Result;
+use std::time::Duration;
+
+// imports the client book import function from the client_books module
+use crate::client_books::{export_clients, import_clients, ExportFormat};
+// imports the client name search items from the client_cache module
+use crate::client_cache::{ClientMatch, NAME_SEARCH_LIMIT};
+// imports the client history search & export items from the client_history module
+use crate::client_history::{
+    export_history, format_timestamp, parse_date, ClientHistoryEntry, HistoryAction,
+    HistoryFilter, HISTORY_PAGE_SIZE,
+};
 
 // imports all public items from the database module
 use crate::database::*;
 // imports all public items from the util module
-use crate::util::get_integer_input;
+use crate::util::{get_integer_input, get_password_input, get_text_input};
+// imports the Session & password items from the auth module
+use crate::auth::{prompt_new_password, reauthenticate, Authenticator, Session};
+// imports the Client & Employee structs from the firm_models module
+use crate::firm_models::{Client, Employee, EmployeeRole};
 // imports all public items from the operation_handlers module
 use crate::operation_handlers::*;
 // imports all public items from the errors module
@@ -34,11 +50,14 @@ use crate::errors::ApplicationError;
 ///
 ///* `client_handler` - Dependency manages client specific operations
 ///* `employee_handler` - Dependency manages employee specific operations
+///* `session` - the signed in employee, operations are attributed to & permission checked against them
+///* `idle_timeout` - inactivity after which the employee must re-authenticate, None to never lock
 ///
-#[allow(dead_code)] // since employee_handler is not actively used
 pub struct Menu {
     client_handler: ClientHandler,
     employee_handler: EmployeeHandler,
+    session: Session,
+    idle_timeout: Option<Duration>,
 }
 
 impl Menu {
@@ -51,6 +70,8 @@ impl Menu {
     ///# Arguments
     ///
     ///* 'database' - boxed trait obj that implements DatabaseManager
+    ///* 'session' - the Session returned by login_handler
+    ///* 'idle_timeout' - the timeout returned by load_idle_timeout
     ///
     ///# Returns
     ///
@@ -62,14 +83,81 @@ impl Menu {
     /// Error occurs if either dependencies fail to initialize,
     /// client_handler, or employee_handler
     ///
-    pub fn new(database: Box<dyn DatabaseManager>) -> Result<Self, ApplicationError> {
+    pub fn new(
+        database: Box<dyn DatabaseManager>,
+        session: Session,
+        idle_timeout: Option<Duration>,
+    ) -> Result<Self, ApplicationError> {
         let client_handler = ClientHandler::new(database.clone_box())?;
         let employee_handler = EmployeeHandler::new(database)?;
         Ok(Self {
             client_handler,
             employee_handler,
+            session,
+            idle_timeout,
         })
     }
+
+    /// checks the session for inactivity after a menu choice was entered
+    ///
+    /// when the employee was idle for longer than the idle_timeout, the
+    /// choice is discarded and the employee must re-authenticate.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(true)' - the session is active, act on the entered choice
+    ///* 'Ok(false)' - the session timed out, and was re-authenticated. The entered
+    ///  choice is discarded
+    ///* 'Err(ApplicationError::AuthenticationError)' - re-authentication failed,
+    ///  the session has ended
+    ///
+    fn check_session(&mut self) -> Result<bool, ApplicationError> {
+        if !self.session.is_idle(self.idle_timeout) {
+            self.session.touch();
+            return Ok(true);
+        }
+        if reauthenticate(&mut self.employee_handler, &mut self.session)? {
+            println!("\nSession unlocked, please select the menu option again.");
+            Ok(false)
+        } else {
+            Err(ApplicationError::AuthenticationError(
+                "Re-authentication failed, the session has ended".to_string(),
+            ))
+        }
+    }
+    /// checks the session for inactivity after any other prompt was answered
+    ///
+    /// wraps the result of an input helper (get_integer_input, get_text_input,
+    /// get_password_input, prompt_new_password), so that every prompt of every
+    /// menu checks the idle_timeout, not only the menu choices.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///* 'input' - the result of the input helper
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(T)' - the session is active, the entered input
+    ///* 'Err(ApplicationError::InputError)' - the session timed out, and was
+    ///  re-authenticated. The entered input is discarded, and the operation cancelled
+    ///* 'Err(ApplicationError::AuthenticationError)' - re-authentication failed,
+    ///  the session has ended
+    ///
+    fn check_input<T>(&mut self, input: Result<T, ApplicationError>) -> Result<T, ApplicationError> {
+        let input = input?;
+        if self.check_session()? {
+            Ok(input)
+        } else {
+            Err(ApplicationError::InputError(
+                "the session had timed out, the operation was cancelled".to_string(),
+            ))
+        }
+    }
     /// Executes looping for the main Menu system
     ///
     /// This method implements the primary menu looping operation,
@@ -93,30 +181,62 @@ impl Menu {
         loop {
             self.display_menu();
             let menu_choice = get_integer_input()?;
+            match self.check_session() {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(ApplicationError::AuthenticationError(reason)) => {
+                    println!("\n{}. Goodbye.", reason);
+                    break;
+                }
+                Err(e) => return Err(e),
+            }
 
-            match MainMenuChoice::convert_i32(menu_choice) {
+            let (result, context) = match MainMenuChoice::convert_i32(menu_choice) {
                 Some(MainMenuChoice::PrintClientList) => {
-                    if let Err(e) = self.display_clients_handler() {
-                        println!("\nError displaying clients: {}", e);
-                    }
+                    (self.display_clients_handler(), "displaying clients")
                 }
                 Some(MainMenuChoice::ChangeServiceChoice) => {
-                    if let Err(e) = self.change_service_handler() {
-                        println!("\nError changing service: {}", e);
-                    }
+                    (self.change_service_handler(), "changing service")
                 }
                 Some(MainMenuChoice::ChangeClientEmployeePair) => {
-                    if let Err(e) = self.change_client_employee_pair() {
-                        println!("\nError changing client pairing: {}", e);
-                    }
+                    (self.change_client_employee_pair(), "changing client pairing")
+                }
+                Some(MainMenuChoice::ImportClients) => {
+                    (self.import_clients_handler(), "importing clients")
+                }
+                Some(MainMenuChoice::ExportClients) => {
+                    (self.export_clients_handler(), "exporting clients")
+                }
+                Some(MainMenuChoice::AddClient) => (self.add_client_handler(), "adding client"),
+                Some(MainMenuChoice::RemoveClient) => {
+                    (self.remove_client_handler(), "removing client")
+                }
+                Some(MainMenuChoice::ManageEmployees) => {
+                    (self.employee_admin_handler(), "managing employees")
+                }
+                Some(MainMenuChoice::ReviewClientHistory) => {
+                    (self.client_history_handler(), "reviewing client history")
+                }
+                Some(MainMenuChoice::ChangePassword) => {
+                    (self.change_password_handler(), "changing password")
                 }
                 Some(MainMenuChoice::ExitProgram) => {
-                    println!("\nGoodbye.");
+                    println!("\nGoodbye, {}.", self.session.get_employee_name());
                     break;
                 }
                 _ => {
                     println!("\nSelect a valid menu option.");
+                    continue;
                 }
+            };
+            match result {
+                Ok(()) => {}
+                // failed re-authentication inside any handler ends the session
+                Err(ApplicationError::AuthenticationError(reason)) => {
+                    println!("\n{}. Goodbye.", reason);
+                    break;
+                }
+                Err(e) => println!("\nError {}: {}", context, e),
             }
         }
         Ok(())
@@ -143,14 +263,18 @@ impl Menu {
     /// the provided client_id does not match an existing client.
     ///
     fn customer_choice_handler(&mut self) -> Result<(), ApplicationError> {
-        let client_id = get_integer_input()?;
-        match self.client_handler.get_client(client_id) {
+        let client_id = match self.select_client()? {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        match self.client_handler.get_client(client_id).cloned() {
             Ok(client) => {
                 let new_service = self.select_valid_service()?;
                 if new_service != ClientServiceChoice::ReturnMenu {
-                    let mut updated_client = client.clone();
+                    let mut updated_client = client;
                     updated_client.change_client_service(new_service as i32);
-                    self.client_handler.update_client(&updated_client)?;
+                    self.client_handler
+                        .update_client(&self.session, &updated_client)?;
                 }
                 Ok(()) // ok result when client found
             }
@@ -178,7 +302,8 @@ impl Menu {
     ///
     fn change_service_handler(&mut self) -> Result<(), ApplicationError> {
         println!("\nYou chose option: Change Client Service Choice");
-        println!("Please enter the client ID of the client you would like to modify.");
+        println!("Please enter the client ID, or name, of the client you would like to modify.");
+        println!("You may also enter 0 to return to the previous menu.\n");
         self.customer_choice_handler()?;
         Ok(())
     }
@@ -200,6 +325,7 @@ impl Menu {
     fn display_clients_handler(&mut self) -> Result<(), ApplicationError> {
         println!("\nYou chose option: Print a Client List");
         println!("Provide the employee ID whose client list you would like to return.");
+        println!("You may also enter 0 to list every client in the firm.");
         self.display_clients()?;
         Ok(())
     }
@@ -222,7 +348,7 @@ impl Menu {
     ///     on fail:
     ///         ApplicationError - If an error occurs due to a failure at any point of the
     ///             call chain, it will be returned here
-
+    ///
     ///# Errors
     ///
     /// This function returns the error : DatabaseError::NotFoundError if
@@ -231,7 +357,7 @@ impl Menu {
     fn change_client_employee_pair(&mut self) -> Result<(), ApplicationError> {
         println!("\nYou chose option: Change Client / Employee pairing");
         println!(
-            "Please enter the client ID of the client whose pairing you would like to change."
+            "Please enter the client ID, or name, of the client whose pairing you would like to change."
         );
         println!("You may also enter 0 to return to the previous menu.\n");
         self.client_pairing_handler()
@@ -260,14 +386,17 @@ impl Menu {
     ///
     ///# Behaviors
     ///
-    ///* 1. Prompts user to provide a client_id integer
+    ///* 1. Prompts user to provide a client_id integer, or a name to search for
     ///* 2. attempts to locate an existing client object using ID value
     ///* 3. if located, prompts to provide the employee_id value for new pairing
     ///* 4. if valid employee match found, updates the client's employee pairing
     ///* 5. updates the user with output related to their provided values
     ///
     fn client_pairing_handler(&mut self) -> Result<(), ApplicationError> {
-        let client_id = get_integer_input()?;
+        let client_id = match self.select_client()? {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
 
         let client = match self.client_handler.get_client(client_id) {
             Ok(client_match) => client_match.clone(), // clone here, to not upset borrow checker
@@ -283,7 +412,10 @@ impl Menu {
             let mut updated_client = client;
             updated_client.change_client_employee_pair(new_employee_id);
 
-            match self.client_handler.update_client(&updated_client) {
+            match self
+                .client_handler
+                .update_client(&self.session, &updated_client)
+            {
                 Ok(_) => {
                     println!(
                         "Client: {} is now paired with Employee: {}",
@@ -298,6 +430,97 @@ impl Menu {
         Ok(())
     }
 
+    /// client selection function, by client ID or name
+    ///
+    /// reads a client ID, or a name to search for. For a name, lists the
+    /// best matches from ClientHandler::search_clients_by_name, and the
+    /// user picks one of them by its number in the list. Advisors are
+    /// only listed the clients assigned to them.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<i32>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Some(i32)) - the entered or picked client id, not yet checked to exist
+    ///    * Ok(None) - 0 was entered, return to the previous menu
+    ///  * on fail:
+    ///    * ApplicationError - input error
+    ///
+    ///# Behaviors
+    ///
+    ///* 1. prompts for a client ID or name, an integer is returned as the client ID
+    ///* 2. otherwise searches the client names, re-prompting when nothing matches
+    ///* 3. outputs the numbered matches the employee may modify, best first
+    ///* 4. prompts for the number of a match, or 0 to search again
+    ///
+    fn select_client(&mut self) -> Result<Option<i32>, ApplicationError> {
+        loop {
+            let input = self.check_input(get_text_input("Client ID or name"))?;
+            if let Ok(client_id) = input.parse::<i32>() {
+                return Ok(if client_id == 0 { None } else { Some(client_id) });
+            }
+
+            // advisors are only offered their own clients, the search is limited after filtering
+            let session = &self.session;
+            let client_handler = &self.client_handler;
+            let matches: Vec<ClientMatch> = client_handler
+                .search_clients_by_name(&input, usize::MAX)
+                .into_iter()
+                .filter(|found| {
+                    client_handler
+                        .authorize_client(session, found.client.get_client_id())
+                        .is_ok()
+                })
+                .take(NAME_SEARCH_LIMIT)
+                .collect();
+            if matches.is_empty() {
+                println!("No clients found matching the name: {}", input);
+                continue;
+            }
+            self.display_client_matches(&matches);
+            let match_ids: Vec<i32> = matches
+                .iter()
+                .map(|found| found.client.get_client_id())
+                .collect();
+            println!("\nEnter the number of the client, or 0 to search again.");
+            loop {
+                match self.check_input(get_integer_input())? {
+                    0 => break,
+                    choice if choice >= 1 && (choice as usize) <= match_ids.len() => {
+                        return Ok(Some(match_ids[choice as usize - 1]));
+                    }
+                    _ => println!("Please enter a valid selection."),
+                }
+            }
+        }
+    }
+
+    /// console output function for the matches of a client name search
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - Reference to self
+    ///* 'matches: &[ClientMatch]' - the matches, numbered from 1 in order
+    ///
+    fn display_client_matches(&self, matches: &[ClientMatch]) {
+        println!("\n# | ID# | Client's Name | Employee ID# | Match");
+        println!("¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯");
+        for (number, found) in matches.iter().enumerate() {
+            println!(
+                "{}: | {}.  | {} | {} | {}",
+                number + 1,
+                found.client.get_client_id(),
+                found.client.get_client_name(),
+                found.client.get_asn_employee(),
+                found.name_match.name()
+            );
+        }
+    }
+
     /// new employee for client pairing selection function
     ///
     /// handle gathering of new employee_id for client-employee pairing changes
@@ -324,7 +547,7 @@ impl Menu {
             println!(
                 "\nPlease enter the ID of the employee you would like to pair with the client. Enter 0 to return to previous menu."
             );
-            let target_employee_id = get_integer_input()?;
+            let target_employee_id = self.check_input(get_integer_input())?;
             if target_employee_id == 0 {
                 return Ok(0); // return to previous menu selected
             }
@@ -343,6 +566,811 @@ impl Menu {
         }
     }
 
+    /// client book import manager function
+    ///
+    /// outputs a confirmation message notifying the user of their selection,
+    /// asks for the path of the CSV file to import, then imports it and
+    /// outputs the per-row report.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, including rows being rejected.
+    ///* 'Err(ApplicationError)' - the file could not be read / has the wrong format
+    ///
+    fn import_clients_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Import clients from a CSV file");
+        println!("The file must use the columns: client_name,client_service,assigned_employee");
+        let path = self.check_input(get_text_input(
+            "Enter the CSV file path (e.g. clients_list.csv)",
+        ))?;
+        let report = import_clients(
+            Path::new(&path),
+            &self.session,
+            &mut self.client_handler,
+            &mut self.employee_handler,
+        )?;
+        report.print();
+        Ok(())
+    }
+
+    /// client book export manager function
+    ///
+    /// outputs a confirmation message notifying the user of their selection,
+    /// then gathers the employee id (0 for the whole firm), the export format,
+    /// and the destination file path before writing the export.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / file could not be written)
+    ///
+    fn export_clients_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Export a client list");
+        println!("Provide the employee ID whose client list you would like to export.");
+        println!("You may also enter 0 to export the client list of the whole firm.");
+        let employee_id = match self.check_input(get_integer_input())? {
+            0 => None,
+            employee_id => Some(employee_id),
+        };
+
+        let format = loop {
+            println!("\nExport format - Options include:");
+            println!("0: Return to previous menu");
+            println!("1: CSV (same format as clients_list.csv)");
+            println!("2: JSON");
+            match self.check_input(get_integer_input())? {
+                0 => return Ok(()),
+                1 => break ExportFormat::Csv,
+                2 => break ExportFormat::Json,
+                _ => println!("Valid options are: 0, 1, or 2. Please enter a valid selection."),
+            }
+        };
+
+        let path = self.check_input(get_text_input("Enter the file path to export to"))?;
+        let exported = export_clients(&self.client_handler, employee_id, format, Path::new(&path))?;
+        println!("Exported {} client(s) to {}", exported, path);
+        Ok(())
+    }
+
+    /// Manages operations related to onboarding a new client
+    ///
+    /// gathers the new client's name, service choice, and assigned
+    /// employee, then adds the client through the ClientHandler and
+    /// outputs the resulting client ID.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    ///# Behaviors
+    ///
+    ///* 1. Prompts user to provide the client's name
+    ///* 2. prompts for the client's service choice, 0 returns to the menu
+    ///* 3. prompts for a valid employee id to pair with, 0 returns to the menu
+    ///* 4. adds the client, and outputs the new client ID
+    ///
+    fn add_client_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Add a new client");
+        let name = self.check_input(get_text_input("Enter the new client's full name"))?;
+
+        let service = self.select_valid_service()?;
+        if service == ClientServiceChoice::ReturnMenu {
+            return Ok(());
+        }
+
+        let employee_id = self.get_new_pair_employee_id()?;
+        if employee_id == 0 {
+            return Ok(());
+        }
+
+        let client = Client::new(0, name, service as i32, employee_id);
+        let client_id = self.client_handler.new_client(&self.session, &client)?;
+        println!(
+            "Added Client: {} {}, paired with Employee: {}",
+            client_id,
+            client.get_client_name(),
+            employee_id
+        );
+        Ok(())
+    }
+
+    /// Manages operations related to offboarding a client
+    ///
+    /// locates the client by their client ID, then asks for
+    /// confirmation before removing them through the ClientHandler.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or cancelled.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn remove_client_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Remove a client");
+        println!("Please enter the client ID, or name, of the client you would like to remove.");
+        println!("You may also enter 0 to return to the previous menu.\n");
+        let client_id = match self.select_client()? {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+
+        let client = match self.client_handler.get_client(client_id) {
+            Ok(client_match) => client_match.clone(),
+            Err(e) => {
+                println!("An error occurred while locating the client: {}", e);
+                return Ok(());
+            }
+        };
+
+        println!(
+            "\nRemove Client: {} {}? Enter 1 to confirm, or 0 to cancel.",
+            client_id,
+            client.get_client_name()
+        );
+        if self.check_input(get_integer_input())? != 1 {
+            println!("Removal cancelled.");
+            return Ok(());
+        }
+        self.client_handler.remove_client(&self.session, &client)?;
+        println!("Removed Client: {}", client_id);
+        Ok(())
+    }
+
+    /// Manages operations related to reviewing the client history
+    ///
+    /// gathers the search filters, then pages back & forth through the matching
+    /// reassignments & service changes, HISTORY_PAGE_SIZE at a time.
+    /// The matches may be exported to a CSV file from any page.
+    /// Only supervisors and admins may review the client history.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (permission / input error / file could not be written)
+    ///
+    ///# Behaviors
+    ///
+    ///* 1. prompts for a client id, employee id, action & date range, each may be left as any
+    ///* 2. outputs a page of matching entries, oldest first
+    ///* 3. prompts for the next or previous page, a CSV export, or a return to the menu
+    ///
+    fn client_history_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Review client history");
+        // checked before prompting for filters, so advisors are not asked for any
+        ClientHandler::authorize_history(&self.session)?;
+
+        let filter = self.select_history_filter()?;
+        let entries = self.client_handler.get_client_history(&self.session, &filter)?;
+        if entries.is_empty() {
+            println!("No client history matches the search.");
+            return Ok(());
+        }
+
+        let pages = entries.len().div_ceil(HISTORY_PAGE_SIZE);
+        let mut page = 0;
+        loop {
+            let start = page * HISTORY_PAGE_SIZE;
+            let end = (start + HISTORY_PAGE_SIZE).min(entries.len());
+            self.display_history_page(&entries[start..end]);
+            println!(
+                "\nPage {} of {} ({} matching entries)",
+                page + 1,
+                pages,
+                entries.len()
+            );
+            loop {
+                println!("0: Return to previous menu");
+                if page + 1 < pages {
+                    println!("1: Next page");
+                }
+                println!("2: Export all matching entries to CSV");
+                if page > 0 {
+                    println!("3: Previous page");
+                }
+                match self.check_input(get_integer_input())? {
+                    0 => return Ok(()),
+                    1 if page + 1 < pages => {
+                        page += 1;
+                        break;
+                    }
+                    2 => {
+                        let path =
+                            self.check_input(get_text_input("Enter the file path to export to"))?;
+                        let exported = export_history(&entries, Path::new(&path))?;
+                        println!("Exported {} history entries to {}", exported, path);
+                        return Ok(());
+                    }
+                    3 if page > 0 => {
+                        page -= 1;
+                        break;
+                    }
+                    _ => println!("Please enter a valid selection."),
+                }
+            }
+        }
+    }
+
+    /// client history search sub-menu function
+    ///
+    /// prompts for each HistoryFilter field in turn. Entering 0 for
+    /// any prompt leaves that field unset, so it matches any entry.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<HistoryFilter, ApplicationError>' - the search, or an input error
+    ///
+    fn select_history_filter(&mut self) -> Result<HistoryFilter, ApplicationError> {
+        let mut filter = HistoryFilter::default();
+
+        println!("\nEnter the client ID to search for, or 0 for any client.");
+        filter.client_id = match self.check_input(get_integer_input())? {
+            0 => None,
+            client_id => Some(client_id),
+        };
+
+        println!("\nEnter the employee ID to search for, or 0 for any employee.");
+        println!("Matches changes made by the employee, and clients reassigned to / from them.");
+        filter.employee_id = match self.check_input(get_integer_input())? {
+            0 => None,
+            employee_id => Some(employee_id),
+        };
+
+        filter.action = loop {
+            println!("\nAction type - Options include:");
+            println!("0: Any");
+            println!("1: Reassignments");
+            println!("2: Service changes");
+            match self.check_input(get_integer_input())? {
+                0 => break None,
+                value => match HistoryAction::convert_i32(value) {
+                    Some(action) => break Some(action),
+                    None => println!("Valid options are: 0, 1, or 2. Please enter a valid selection."),
+                },
+            }
+        };
+
+        filter.from = self.select_history_date(
+            "Enter the first date to include (YYYY-MM-DD), or 0 for no start date",
+            false,
+        )?;
+        filter.until = self.select_history_date(
+            "Enter the last date to include (YYYY-MM-DD), or 0 for no end date",
+            true,
+        )?;
+        Ok(filter)
+    }
+
+    /// client history date input function
+    ///
+    /// continues prompting until a valid YYYY-MM-DD date, or 0, is entered.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///* 'prompt: &str' - the text displayed before reading the date
+    ///* 'end_of_day: bool' - true for the end of a range, see parse_date
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<i64>, ApplicationError>' - the timestamp, None when 0 was entered
+    ///
+    fn select_history_date(
+        &mut self,
+        prompt: &str,
+        end_of_day: bool,
+    ) -> Result<Option<i64>, ApplicationError> {
+        loop {
+            let input = self.check_input(get_text_input(prompt))?;
+            if input == "0" {
+                return Ok(None);
+            }
+            match parse_date(&input, end_of_day) {
+                Ok(timestamp) => return Ok(Some(timestamp)),
+                Err(e) => println!("{}", e),
+            }
+        }
+    }
+
+    /// console output function for a page of client history entries
+    ///
+    /// reassignments show the old / new employee ids, service changes
+    /// show the old / new service values.
+    ///
+    ///# Arguments
+    ///
+    ///* '&self' - Reference to self
+    ///* 'entries: &[ClientHistoryEntry]' - the entries of the page
+    ///
+    fn display_history_page(&self, entries: &[ClientHistoryEntry]) {
+        println!("\nDate (UTC) | Client ID# | Changed By | Action | Old -> New");
+        println!("¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯");
+        for entry in entries {
+            println!(
+                "{} | {} | {} | {} | {} -> {}",
+                format_timestamp(entry.occurred_at),
+                entry.client_id,
+                entry.changed_by,
+                entry.action.name(),
+                entry.old_value,
+                entry.new_value
+            );
+        }
+    }
+
+    /// Manages operations related to changing the signed in employee's password
+    ///
+    /// verifies the employee's current password, then prompts for a new
+    /// password that meets the PasswordPolicy, and saves it through the
+    /// EmployeeHandler.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or the current password was wrong.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn change_password_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Change my password");
+        let employee_id = self.session.get_employee_id();
+        let employee = self.employee_handler.get_employee(employee_id)?.ok_or_else(|| {
+            ApplicationError::NoMatchError(format!("No employee found with the ID: {}", employee_id))
+        })?;
+
+        let current = self.check_input(get_password_input("Enter your current password"))?;
+        // checked before prompting for the new password, change_own_password checks it again
+        if !Authenticator::verify_password(employee.get_employee_hash(), &current) {
+            println!("The current password is incorrect, the password was not changed.");
+            return Ok(());
+        }
+        let password = loop {
+            let password = self.check_input(prompt_new_password())?;
+            if password != current {
+                break password;
+            }
+            println!("\nThe new password must differ from the current password.");
+        };
+        self.employee_handler
+            .change_own_password(&self.session, &current, &password)?;
+        println!("Your password has been changed.");
+        Ok(())
+    }
+
+    /// Executes looping for the employee administration sub-menu
+    ///
+    /// Provides the employee administration actions until the user
+    /// returns to the main menu. Errors from a single action are
+    /// output, and the sub-menu continues. Actions include:
+    /// - Listing employees
+    /// - Adding, renaming, and deleting employees
+    /// - Resetting an employee's password
+    /// - Changing an employee's role
+    /// - Unlocking an employee's login
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when the user returns to the main menu
+    ///* 'Err(ApplicationError)' - on failure reading input
+    ///
+    fn employee_admin_handler(&mut self) -> Result<(), ApplicationError> {
+        println!("\nYou chose option: Manage employees");
+        loop {
+            println!("\nEmployee Administration Sub-menu - Options include:");
+            println!("0: Return to previous menu");
+            println!("1: List employees");
+            println!("2: Add a new employee");
+            println!("3: Rename an employee");
+            println!("4: Reset an employee's password");
+            println!("5: Delete an employee");
+            println!("6: Change an employee's role");
+            println!("7: Unlock an employee's login");
+            let choice = get_integer_input()?;
+            if !self.check_session()? {
+                continue;
+            }
+            let result = match EmployeeMenuChoice::convert_i32(choice) {
+                Some(EmployeeMenuChoice::ReturnMenu) => return Ok(()),
+                Some(EmployeeMenuChoice::ListEmployees) => self.list_employees(),
+                Some(EmployeeMenuChoice::AddEmployee) => self.add_employee_handler(),
+                Some(EmployeeMenuChoice::RenameEmployee) => self.rename_employee_handler(),
+                Some(EmployeeMenuChoice::ResetPassword) => self.reset_password_handler(),
+                Some(EmployeeMenuChoice::DeleteEmployee) => self.delete_employee_handler(),
+                Some(EmployeeMenuChoice::ChangeRole) => self.change_role_handler(),
+                Some(EmployeeMenuChoice::UnlockEmployee) => self.unlock_employee_handler(),
+                None => {
+                    println!("Valid options are: 0 - 7. Please enter a valid selection.");
+                    Ok(())
+                }
+            };
+            match result {
+                Ok(()) => {}
+                // the session has ended, run signs the employee out
+                Err(e @ ApplicationError::AuthenticationError(_)) => return Err(e),
+                Err(e) => println!("\nAn error occurred: {}", e),
+            }
+        }
+    }
+
+    /// console output function to list every employee
+    ///
+    /// outputs each employee's id, name, and role, along with the
+    /// number of clients currently assigned to them.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful.
+    ///* 'Err(ApplicationError)' - failure retrieving the employees
+    ///
+    fn list_employees(&mut self) -> Result<(), ApplicationError> {
+        let employees = self.employee_handler.get_employees()?;
+        println!("\nID# | Employee's Name | Role | Assigned Clients");
+        println!("¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯");
+        for employee in &employees {
+            println!(
+                "{}.  | {}   {}   clients: {}",
+                employee.get_employee_id(),
+                employee.get_employee_name(),
+                employee.get_employee_role().name(),
+                self.client_handler
+                    .get_clients_for_employee(employee.get_employee_id())
+                    .map_or(0, |client_ids| client_ids.len())
+            );
+        }
+        Ok(())
+    }
+
+    /// Manages operations related to adding a new employee
+    ///
+    /// gathers the new employee's name, password, and role, then
+    /// adds the employee through the EmployeeHandler.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn add_employee_handler(&mut self) -> Result<(), ApplicationError> {
+        EmployeeHandler::authorize_admin(&self.session)?;
+        let name = self.check_input(get_text_input("Enter the new employee's full name"))?;
+        println!("\nPlease enter the new employee's password: ");
+        let password = self.check_input(prompt_new_password())?;
+        let role = match self.select_role()? {
+            Some(role) => role,
+            None => return Ok(()),
+        };
+        let employee = Employee::new(0, &name, &password, role)?;
+        let employee_id = self
+            .employee_handler
+            .add_new_employee(&self.session, &employee)?;
+        println!("Added Employee: {} {}", employee_id, name);
+        Ok(())
+    }
+
+    /// Manages operations related to renaming an employee
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn rename_employee_handler(&mut self) -> Result<(), ApplicationError> {
+        let mut employee = match self.select_employee("rename")? {
+            Some(employee) => employee,
+            None => return Ok(()),
+        };
+        let name = self.check_input(get_text_input("Enter the employee's new full name"))?;
+        employee.set_employee_name(&name);
+        self.employee_handler
+            .modify_employee(&self.session, &employee)?;
+        println!("Employee: {} is now named {}", employee.get_employee_id(), name);
+        Ok(())
+    }
+
+    /// Manages operations related to resetting an employee's password
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn reset_password_handler(&mut self) -> Result<(), ApplicationError> {
+        let mut employee = match self.select_employee("reset the password of")? {
+            Some(employee) => employee,
+            None => return Ok(()),
+        };
+        println!("\nPlease enter the employee's new password: ");
+        let password = self.check_input(prompt_new_password())?;
+        employee.change_password(&password)?;
+        self.employee_handler
+            .modify_employee(&self.session, &employee)?;
+        println!("Password reset for Employee: {}", employee.get_employee_id());
+        Ok(())
+    }
+
+    /// Manages operations related to deleting an employee
+    ///
+    /// An employee who still has clients assigned cannot be deleted
+    /// as is, since their clients would be paired with a missing employee.
+    /// The user is offered to reassign those clients to another employee,
+    /// or to cancel the deletion. Nothing is changed before the deletion
+    /// is confirmed.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or cancelled.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    ///# Behaviors
+    ///
+    ///* 1. Prompts user to select the employee to delete
+    ///* 2. if the employee has clients, prompts for the employee to reassign them to,
+    ///     or cancels on 0
+    ///* 3. asks for confirmation
+    ///* 4. reassigns every client and deletes the employee in one transaction
+    ///
+    fn delete_employee_handler(&mut self) -> Result<(), ApplicationError> {
+        // checked first, so that no prompts are shown for a deletion that would be denied
+        EmployeeHandler::authorize_admin(&self.session)?;
+        let employee = match self.select_employee("delete")? {
+            Some(employee) => employee,
+            None => return Ok(()),
+        };
+        let employee_id = employee.get_employee_id();
+        if employee_id == self.session.get_employee_id() {
+            println!("You cannot delete your own account.");
+            return Ok(());
+        }
+
+        let client_count = self
+            .client_handler
+            .get_clients_for_employee(employee_id)
+            .map_or(0, |client_ids| client_ids.len());
+        let mut new_employee_id = None;
+        if client_count > 0 {
+            println!(
+                "\nEmployee: {} still has {} client(s) assigned, they must be reassigned.",
+                employee_id, client_count
+            );
+            let reassign_to = loop {
+                let reassign_to = self.get_new_pair_employee_id()?;
+                if reassign_to != employee_id {
+                    break reassign_to;
+                }
+                println!("Clients must be reassigned to a different employee.");
+            };
+            if reassign_to == 0 {
+                println!("Deletion cancelled.");
+                return Ok(());
+            }
+            new_employee_id = Some(reassign_to);
+        }
+
+        match new_employee_id {
+            Some(reassign_to) => println!(
+                "\nReassign {} client(s) to Employee: {}, and delete Employee: {} {}? \
+                 Enter 1 to confirm, or 0 to cancel.",
+                client_count,
+                reassign_to,
+                employee_id,
+                employee.get_employee_name()
+            ),
+            None => println!(
+                "\nDelete Employee: {} {}? Enter 1 to confirm, or 0 to cancel.",
+                employee_id,
+                employee.get_employee_name()
+            ),
+        }
+        if self.check_input(get_integer_input())? != 1 {
+            println!("Deletion cancelled.");
+            return Ok(());
+        }
+
+        match new_employee_id {
+            Some(reassign_to) => {
+                let reassigned = self.employee_handler.reassign_and_delete_employee(
+                    &self.session,
+                    employee_id,
+                    reassign_to,
+                    &mut self.client_handler,
+                )?;
+                println!(
+                    "Reassigned {} client(s) to Employee: {}",
+                    reassigned, reassign_to
+                );
+            }
+            None => self.employee_handler.delete_employee(
+                &self.session,
+                employee_id,
+                &self.client_handler,
+            )?,
+        }
+        println!("Deleted Employee: {}", employee_id);
+        Ok(())
+    }
+
+    /// Manages operations related to changing an employee's role
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn change_role_handler(&mut self) -> Result<(), ApplicationError> {
+        EmployeeHandler::authorize_admin(&self.session)?;
+        let mut employee = match self.select_employee("change the role of")? {
+            Some(employee) => employee,
+            None => return Ok(()),
+        };
+        let role = match self.select_role()? {
+            Some(role) => role,
+            None => return Ok(()),
+        };
+        employee.set_employee_role(role);
+        self.employee_handler
+            .modify_employee(&self.session, &employee)?;
+        println!(
+            "Employee: {} is now an {}",
+            employee.get_employee_id(),
+            role.name()
+        );
+        Ok(())
+    }
+
+    /// Manages operations related to unlocking an employee's login
+    ///
+    /// clears the employee's failed login attempts, ending any lockout.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when operation is successful, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error / application error)
+    ///
+    fn unlock_employee_handler(&mut self) -> Result<(), ApplicationError> {
+        EmployeeHandler::authorize_admin(&self.session)?;
+        let employee = match self.select_employee("unlock")? {
+            Some(employee) => employee,
+            None => return Ok(()),
+        };
+        let employee_id = employee.get_employee_id();
+        match self
+            .employee_handler
+            .unlock_employee(&self.session, employee_id)?
+        {
+            Some(attempts) => println!(
+                "Employee: {} is unlocked, cleared {} failed login attempt(s)",
+                employee_id, attempts.failed_attempts
+            ),
+            None => println!("Employee: {} has no failed login attempts", employee_id),
+        }
+        Ok(())
+    }
+
+    /// employee role selection sub-menu function
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<EmployeeRole>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Some(EmployeeRole)) - the selected role
+    ///    * Ok(None) - return to previous menu selected
+    ///  * on fail:
+    ///    * ApplicationError - failure reading input
+    ///
+    fn select_role(&mut self) -> Result<Option<EmployeeRole>, ApplicationError> {
+        loop {
+            println!("\nEmployee Role Sub-menu - Options include:");
+            println!("0: Return to previous menu");
+            println!("1: Advisor (may only modify their own clients)");
+            println!("2: Supervisor (may modify any client)");
+            println!("3: Admin (may modify any client, and manage employees)");
+            match self.check_input(get_integer_input())? {
+                0 => return Ok(None),
+                value => match EmployeeRole::convert_i32(value) {
+                    Some(role) => return Ok(Some(role)),
+                    None => println!("Valid options are: 0 - 3. Please enter a valid selection."),
+                },
+            }
+        }
+    }
+
+    /// employee selection function for the administration sub-menu
+    ///
+    /// asks for an employee id until a valid employee is found, or
+    /// the user enters 0 to return to the previous menu.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - mutable reference to self
+    ///* 'action: &str' - the action named in the prompt, e.g. "rename"
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<Employee>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Some(Employee)) - the selected employee
+    ///    * Ok(None) - return to previous menu selected
+    ///  * on fail:
+    ///    * ApplicationError - failure reading input / locating the employee
+    ///
+    fn select_employee(&mut self, action: &str) -> Result<Option<Employee>, ApplicationError> {
+        loop {
+            println!(
+                "\nPlease enter the ID of the employee you would like to {}. Enter 0 to return to previous menu.",
+                action
+            );
+            let employee_id = self.check_input(get_integer_input())?;
+            if employee_id == 0 {
+                return Ok(None);
+            }
+            match self.employee_handler.get_employee(employee_id)? {
+                Some(employee) => return Ok(Some(employee)),
+                None => println!("Invalid ID provided. Please enter a valid employee ID."),
+            }
+        }
+    }
+
     //
     //
     //
@@ -374,7 +1402,10 @@ impl Menu {
     /// 6. else no clients found, outputs error, none found message
     ///
     fn display_clients(&mut self) -> Result<(), ApplicationError> {
-        let employee_id = get_integer_input()?;
+        let employee_id = self.check_input(get_integer_input())?;
+        if employee_id == 0 {
+            return self.display_all_clients();
+        }
         match self.client_handler.get_clients_for_employee(employee_id) {
             Some(client_ids) => {
                 println!("\nClients for Employee ID: {}", employee_id);
@@ -405,20 +1436,83 @@ impl Menu {
         Ok(())
     }
 
+    /// console output function to page through every client
+    ///
+    /// outputs every client in the firm in client id order,
+    /// CLIENT_PAGE_SIZE at a time. Each page starts after the last
+    /// client id of the previous one.
+    ///
+    ///# Arguments
+    ///
+    ///* '&mut self' - Reference to mutable self
+    ///
+    ///# Returns
+    ///
+    ///* 'Ok(())' - when the last page was displayed, or returned to the previous menu.
+    ///* 'Err(ApplicationError)' - on failure (input error)
+    ///
+    fn display_all_clients(&mut self) -> Result<(), ApplicationError> {
+        let total = self.client_handler.client_count();
+        if total == 0 {
+            println!("\nNo clients found.");
+            return Ok(());
+        }
+        let pages = total.div_ceil(CLIENT_PAGE_SIZE);
+        let mut after = None;
+        for page in 0..pages {
+            let clients = self.client_handler.get_clients_page(after, CLIENT_PAGE_SIZE);
+            println!("\nID# | Client's Name | Service Selected (1 = Brokerage, 2 = Retirement) | Employee ID#");
+            println!("¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯¯");
+            for client in &clients {
+                println!(
+                    "{}.  | {}   selected option {} | {}",
+                    client.get_client_id(),
+                    client.get_client_name(),
+                    client.get_client_service(),
+                    client.get_asn_employee()
+                );
+            }
+            after = clients.last().map(|client| client.get_client_id());
+            println!("\nPage {} of {} ({} clients)", page + 1, pages, total);
+            if page + 1 == pages {
+                break;
+            }
+            loop {
+                println!("0: Return to previous menu");
+                println!("1: Next page");
+                match self.check_input(get_integer_input())? {
+                    0 => return Ok(()),
+                    1 => break,
+                    _ => println!("Please enter a valid selection."),
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// main menu console display / output function
     ///
-    /// outputs the list of possible menu choices to the console
+    /// outputs the signed in employee, then the list of possible
+    /// menu choices to the console
     ///
     ///# Arguments
     ///
     ///* '&self' - Reference to self
     ///
     fn display_menu(&self) {
-        println!("\nWhat would you like to do?");
+        println!("\n{}", self.session.prompt());
+        println!("What would you like to do?");
         println!("DISPLAY the client list (enter 1)");
         println!("CHANGE a client's choice (enter 2)");
         println!("CHANGE a client's employee pairing (enter 3)");
-        println!("Exit the program.. (enter 4)");
+        println!("IMPORT clients from a CSV file (enter 4)");
+        println!("EXPORT a client list to CSV or JSON (enter 5)");
+        println!("ADD a new client (enter 6)");
+        println!("REMOVE a client (enter 7)");
+        println!("MANAGE employees (enter 8)");
+        println!("REVIEW client reassignment & service history (enter 9)");
+        println!("CHANGE my password (enter 10)");
+        println!("Exit the program.. (enter 11)");
         println!("\nPlease provide a selection matching a valid menu option. ");
     }
 
@@ -430,13 +1524,13 @@ impl Menu {
     ///
     ///# Arguments
     ///
-    ///* '&self' - Reference to self
+    ///* '&mut self' - Reference to mutable self
     ///
     ///# Returns
     ///
     ///* 'ClientServiceChoice' - the selected ClientServiceChoice enum variant
     ///
-    fn select_valid_service(&self) -> Result<ClientServiceChoice, ApplicationError> {
+    fn select_valid_service(&mut self) -> Result<ClientServiceChoice, ApplicationError> {
         loop {
             println!("\nClient Service Sub-menu - Options include:");
             println!("0: Return to previous menu");
@@ -445,7 +1539,7 @@ impl Menu {
             println!(
                 "\nPlease enter the client's new service choice, or 0 to return to previous menu."
             );
-            let service_choice = get_integer_input()?;
+            let service_choice = self.check_input(get_integer_input())?;
 
             if let Some(service) = ClientServiceChoice::convert_i32(service_choice) {
                 return Ok(service);
@@ -467,7 +1561,15 @@ impl Menu {
 ///* `DefaultMenuValue` - default menu choice (-1)
 ///* `PrintClientList` - option to print client list (1)
 ///* `ChangeServiceChoice` - option to change client service (2)
-///* `ExitProgram` - option for exit program (3)
+///* `ChangeClientEmployeePair` - option to change client / employee pairing (3)
+///* `ImportClients` - option to import clients from a CSV file (4)
+///* `ExportClients` - option to export a client list to CSV / JSON (5)
+///* `AddClient` - option to add a new client (6)
+///* `RemoveClient` - option to remove a client (7)
+///* `ManageEmployees` - option for the employee administration sub-menu (8)
+///* `ReviewClientHistory` - option to search & export the client history (9)
+///* `ChangePassword` - option to change the signed in employee's password (10)
+///* `ExitProgram` - option for exit program (11)
 ///
 #[derive(Clone, Debug, PartialEq)]
 pub enum MainMenuChoice {
@@ -475,7 +1577,14 @@ pub enum MainMenuChoice {
     PrintClientList = 1,
     ChangeServiceChoice = 2,
     ChangeClientEmployeePair = 3,
-    ExitProgram = 4,
+    ImportClients = 4,
+    ExportClients = 5,
+    AddClient = 6,
+    RemoveClient = 7,
+    ManageEmployees = 8,
+    ReviewClientHistory = 9,
+    ChangePassword = 10,
+    ExitProgram = 11,
 }
 
 impl MainMenuChoice {
@@ -499,11 +1608,74 @@ impl MainMenuChoice {
             1 => Some(MainMenuChoice::PrintClientList),
             2 => Some(MainMenuChoice::ChangeServiceChoice),
             3 => Some(MainMenuChoice::ChangeClientEmployeePair),
-            4 => Some(MainMenuChoice::ExitProgram),
+            4 => Some(MainMenuChoice::ImportClients),
+            5 => Some(MainMenuChoice::ExportClients),
+            6 => Some(MainMenuChoice::AddClient),
+            7 => Some(MainMenuChoice::RemoveClient),
+            8 => Some(MainMenuChoice::ManageEmployees),
+            9 => Some(MainMenuChoice::ReviewClientHistory),
+            10 => Some(MainMenuChoice::ChangePassword),
+            11 => Some(MainMenuChoice::ExitProgram),
             _ => None,
         }
     }
 }
+/// The constant / enum values for handling employee sub-menu options
+///
+/// Enum containing definition of constant values for the
+/// employee administration sub-menu options. Used to map user
+/// inputs to specific employee actions.
+///
+///# Variants
+///
+///* `ReturnMenu` - return to prev menu option (0)
+///* `ListEmployees` - option to list employees (1)
+///* `AddEmployee` - option to add a new employee (2)
+///* `RenameEmployee` - option to rename an employee (3)
+///* `ResetPassword` - option to reset an employee's password (4)
+///* `DeleteEmployee` - option to delete an employee (5)
+///* `ChangeRole` - option to change an employee's role (6)
+///* `UnlockEmployee` - option to unlock an employee's login (7)
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum EmployeeMenuChoice {
+    ReturnMenu = 0,
+    ListEmployees = 1,
+    AddEmployee = 2,
+    RenameEmployee = 3,
+    ResetPassword = 4,
+    DeleteEmployee = 5,
+    ChangeRole = 6,
+    UnlockEmployee = 7,
+}
+
+impl EmployeeMenuChoice {
+    /// Converts user input value to enum variant.
+    ///
+    ///# Arguments
+    ///
+    ///* 'value' - i32 integer user input value for sub-menu choice
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(EmployeeMenuChoice)' - for matching explicitly defined enum variant
+    ///* 'None' - returns None when no match to a defined enum variant
+    ///
+    pub fn convert_i32(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::ReturnMenu),
+            1 => Some(Self::ListEmployees),
+            2 => Some(Self::AddEmployee),
+            3 => Some(Self::RenameEmployee),
+            4 => Some(Self::ResetPassword),
+            5 => Some(Self::DeleteEmployee),
+            6 => Some(Self::ChangeRole),
+            7 => Some(Self::UnlockEmployee),
+            _ => None,
+        }
+    }
+}
+
 /// The constant / enum values for handling menu options
 ///
 /// Enum containing definition of constant values for the
@@ -539,7 +1711,7 @@ impl ClientServiceChoice {
     ///* 'Some(ClientServiceChoice)' - for matching explicitly defined enum variant
     ///* 'None' - returns None when no match to a defined enum variant
     ///
-    fn convert_i32(value: i32) -> Option<Self> {
+    pub fn convert_i32(value: i32) -> Option<Self> {
         match value {
             0 => Some(Self::ReturnMenu),
             1 => Some(Self::Brokerage),
@@ -547,4 +1719,26 @@ impl ClientServiceChoice {
             _ => None,
         }
     }
+
+    /// Converts a service name, or it's number, to enum variant.
+    ///
+    /// Accepts "brokerage" / "retirement" (case insensitive), or the
+    /// same integer values accepted by convert_i32.
+    ///
+    ///# Arguments
+    ///
+    ///* 'value' - service name or number
+    ///
+    ///# Returns
+    ///
+    ///* 'Some(ClientServiceChoice)' - for matching explicitly defined enum variant
+    ///* 'None' - returns None when no match to a defined enum variant
+    ///
+    pub fn from_name(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "brokerage" => Some(Self::Brokerage),
+            "retirement" => Some(Self::Retirement),
+            other => other.parse::<i32>().ok().and_then(Self::convert_i32),
+        }
+    }
 }
@@ -0,0 +1,205 @@
+// migrations.rs
+//
+// Created by Edward Johnson 07/11/24
+// SNHU - CS499 - Final Project
+//
+
+//! This module defines the versioned schema migrations for the
+//! application's database. Each migration is applied once, and the
+//! applied versions are recorded by the backend in a
+//! `schema_migrations` metadata table. This creates the clients and
+//! employees tables on a new deployment, and upgrades existing ones.
+
+// imports all public items from the database module
+use crate::database::DatabaseManager;
+// imports necessary errors from errors module
+use crate::errors::ApplicationError;
+
+//
+// ********************************************
+// migrations.rs module definitions begin here:
+// ********************************************
+//
+
+/// represents a single versioned schema migration
+///
+/// Holds the statements for each SQL dialect the application supports.
+/// Backends without a SQL schema (the in-memory database) only record
+/// the version.
+///
+///# Fields
+///
+///* `version` - i32 integer, unique & increasing migration version
+///* `name` - short description of the schema change
+///* `mysql` - statements run, in order, against the MySQL database
+///* `sqlite` - statements run, in order, against the SQLite database
+///
+#[derive(Debug)]
+pub struct Migration {
+    pub version: i32,
+    pub name: &'static str,
+    pub mysql: &'static [&'static str],
+    pub sqlite: &'static [&'static str],
+}
+
+/// every schema migration, in version order
+///
+/// New schema changes are added to the end of this list with the next
+/// version number. Applied migrations must never be edited.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "create clients and employees tables",
+        mysql: &[
+            "CREATE TABLE IF NOT EXISTS employees (
+            employee_id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+            employee_name VARCHAR(255) NOT NULL,
+            hashed_password VARCHAR(255) NOT NULL
+        )",
+            "CREATE TABLE IF NOT EXISTS clients (
+            client_id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+            client_name VARCHAR(255) NOT NULL,
+            client_service INT NOT NULL,
+            assigned_employee INT NOT NULL,
+            INDEX idx_clients_assigned_employee (assigned_employee)
+        )",
+        ],
+        sqlite: &[
+            "CREATE TABLE IF NOT EXISTS employees (
+            employee_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            employee_name VARCHAR(255) NOT NULL,
+            hashed_password VARCHAR(255) NOT NULL
+        )",
+            "CREATE TABLE IF NOT EXISTS clients (
+            client_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            client_name VARCHAR(255) NOT NULL,
+            client_service INTEGER NOT NULL,
+            assigned_employee INTEGER NOT NULL
+        )",
+            "CREATE INDEX IF NOT EXISTS idx_clients_assigned_employee ON clients (assigned_employee)",
+        ],
+    },
+    Migration {
+        version: 2,
+        name: "add employee roles",
+        // existing employees become advisors. No one is promoted here, the first
+        // admin is added with `employee add --role admin` (EmployeeHandler::bootstrap_admin)
+        mysql: &["ALTER TABLE employees ADD COLUMN employee_role INT NOT NULL DEFAULT 1"],
+        sqlite: &["ALTER TABLE employees ADD COLUMN employee_role INTEGER NOT NULL DEFAULT 1"],
+    },
+    Migration {
+        version: 3,
+        name: "create audit_log table",
+        mysql: &[
+            "CREATE TABLE IF NOT EXISTS audit_log (
+            audit_id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+            occurred_at BIGINT NOT NULL,
+            employee_id INT NOT NULL,
+            action VARCHAR(32) NOT NULL,
+            target_id INT NOT NULL,
+            before_value TEXT NULL,
+            after_value TEXT NULL,
+            INDEX idx_audit_log_target (action, target_id)
+        )",
+        ],
+        sqlite: &[
+            "CREATE TABLE IF NOT EXISTS audit_log (
+            audit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            occurred_at INTEGER NOT NULL,
+            employee_id INTEGER NOT NULL,
+            action VARCHAR(32) NOT NULL,
+            target_id INTEGER NOT NULL,
+            before_value TEXT NULL,
+            after_value TEXT NULL
+        )",
+            "CREATE INDEX IF NOT EXISTS idx_audit_log_target ON audit_log (action, target_id)",
+        ],
+    },
+    Migration {
+        version: 4,
+        name: "create client_history table",
+        mysql: &[
+            "CREATE TABLE IF NOT EXISTS client_history (
+            history_id INT NOT NULL AUTO_INCREMENT PRIMARY KEY,
+            occurred_at BIGINT NOT NULL,
+            client_id INT NOT NULL,
+            changed_by INT NOT NULL,
+            action INT NOT NULL,
+            old_value INT NOT NULL,
+            new_value INT NOT NULL,
+            INDEX idx_client_history_client (client_id),
+            INDEX idx_client_history_occurred (occurred_at)
+        )",
+        ],
+        sqlite: &[
+            "CREATE TABLE IF NOT EXISTS client_history (
+            history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+            occurred_at INTEGER NOT NULL,
+            client_id INTEGER NOT NULL,
+            changed_by INTEGER NOT NULL,
+            action INTEGER NOT NULL,
+            old_value INTEGER NOT NULL,
+            new_value INTEGER NOT NULL
+        )",
+            "CREATE INDEX IF NOT EXISTS idx_client_history_client ON client_history (client_id)",
+            "CREATE INDEX IF NOT EXISTS idx_client_history_occurred ON client_history (occurred_at)",
+        ],
+    },
+    Migration {
+        version: 5,
+        name: "create login_attempts table",
+        mysql: &[
+            "CREATE TABLE IF NOT EXISTS login_attempts (
+            employee_id INT NOT NULL PRIMARY KEY,
+            failed_attempts INT NOT NULL,
+            last_failed_at BIGINT NOT NULL,
+            locked_until BIGINT NOT NULL
+        )",
+        ],
+        sqlite: &[
+            "CREATE TABLE IF NOT EXISTS login_attempts (
+            employee_id INTEGER NOT NULL PRIMARY KEY,
+            failed_attempts INTEGER NOT NULL,
+            last_failed_at INTEGER NOT NULL,
+            locked_until INTEGER NOT NULL
+        )",
+        ],
+    },
+];
+
+/// Applies every pending migration to the database
+///
+/// compares the versions recorded by the database against the
+/// MIGRATIONS list, and applies the missing ones in version order.
+///
+///# Arguments
+///
+///* 'database: &mut dyn DatabaseManager' - mut ref to object implementing DbManager.
+///
+///# Returns
+///
+///* 'Result<Vec<i32>, ApplicationError>' -
+///  * on success:
+///    * Ok(Vec<i32>) - versions applied by this call, empty when up to date
+///  * on fail:
+///    * ApplicationError - the migration that failed, and why. Migrations
+///      before it remain applied.
+///
+pub fn run_migrations(database: &mut dyn DatabaseManager) -> Result<Vec<i32>, ApplicationError> {
+    let applied = database.applied_migrations()?;
+    let mut newly_applied = Vec::new();
+
+    for migration in MIGRATIONS {
+        if applied.contains(&migration.version) {
+            continue;
+        }
+        // stderr, keeps the stdout of subcommands clean for scripts
+        eprintln!(
+            "Applying schema migration {}: {}",
+            migration.version, migration.name
+        );
+        database.apply_migration(migration)?;
+        newly_applied.push(migration.version);
+    }
+    Ok(newly_applied)
+}
@@ -7,20 +7,31 @@
 //! This module provides object handler definitions for the two primary
 //! structures in the application, Employee, and Client.
 //! This includes providing dependencies and consistent operations
-//! throughout the program.
+//! throughout the program. Every mutation is recorded in the audit
+//! log, in the same transaction as the change itself.
 
 // imports the Box struct from the standard library boxed module
 use std::boxed::Box;
 // imports the HashMap struct from the standard library collections module
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+// imports the Deref traits, used by the Transaction's database handle
+use std::ops::{Deref, DerefMut};
 
-// imports all public items from the data_structs module
-use crate::data_structs::*;
+// imports the client cache & search filter from the client_cache module
+use crate::client_cache::{ClientCache, ClientFilter, ClientMatch};
 // imports all public items from the database module
 use crate::database::*;
 // imports all public items from the firm_models module
 use crate::firm_models::*;
 
+// imports the audit log entry items from the audit module
+use crate::audit::{
+    client_snapshot, employee_snapshot, login_attempts_snapshot, AuditAction, AuditEntry,
+};
+// imports the Session & failed login record from the auth module
+use crate::auth::{Authenticator, LoginAttempts, PasswordPolicy, Session};
+// imports the client history items from the client_history module
+use crate::client_history::{ClientHistoryEntry, HistoryAction, HistoryFilter};
 // imports all public items from the errors module
 use crate::errors::ApplicationError;
 
@@ -113,6 +124,60 @@ impl EmployeeHandler {
             None => Ok(None),
         }
     }
+    /// retrieves an employee's failed login record
+    ///
+    /// not cached, failed attempts are persisted so that they are shared
+    /// by every running instance of the application.
+    ///
+    /// # Arguments
+    ///
+    /// * `&self` - ref to instance of employehandler
+    /// * `employee_id: i32` - employee id of the failed login record
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Option<LoginAttempts>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Some(LoginAttempts)) - the employee's failed login record
+    ///    * Ok(None) - the employee has no failed attempts recorded
+    ///  * on fail:
+    ///    * ApplicationError - the relevant Application error
+    ///
+    pub fn get_login_attempts(
+        &self,
+        employee_id: i32,
+    ) -> Result<Option<LoginAttempts>, ApplicationError> {
+        Ok(self.database.get_login_attempts(employee_id)?)
+    }
+    /// Records a failed login of an employee, used by the Authenticator
+    ///
+    /// the database increments the failed attempts within a transaction,
+    /// so failures made at the same time by several instances are all counted.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` - mutable reference to the employeehandler instance
+    /// * `employee_id: i32` - id of the employee the login failed for
+    /// * `now: i64` - unix timestamp of the failed attempt
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<LoginAttempts, ApplicationError>' - the employee's updated failed login record
+    ///
+    pub fn record_login_failure(
+        &mut self,
+        employee_id: i32,
+        now: i64,
+    ) -> Result<LoginAttempts, ApplicationError> {
+        let mut transaction = Transaction::new(&mut self.database)?;
+        let attempts = transaction.db.record_login_failure(employee_id, now)?;
+        transaction.commit()?;
+        Ok(attempts)
+    }
+    /// removes an employee's failed login record after a successful login
+    pub fn clear_login_attempts(&mut self, employee_id: i32) -> Result<(), ApplicationError> {
+        Ok(self.database.clear_login_attempts(employee_id)?)
+    }
     /// retrieves an employee from the db by employee_id
     ///
     /// retrieval function for a specific employee object, used to
@@ -138,7 +203,6 @@ impl EmployeeHandler {
     ///         
     // we will use a somewhat "lazy" approach to caching employee hashes.
     // load them as needed, and then store them locally
-
     pub fn get_employee(&mut self, employee_id: i32) -> Result<Option<Employee>, ApplicationError> {
         // check local storage/cache hashmap for employee
         if let Some(employee) = self.stored_employees.get(&employee_id) {
@@ -185,6 +249,37 @@ impl EmployeeHandler {
         Ok(self.get_employee(employee_id)?.is_some())
     }
 
+    /// retrieves every employee from the database
+    ///
+    /// loads all employees, ordered by employee id, and refreshes the
+    /// local stored employee and stored hashes hashmaps with them.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable ref to instance of employehandler
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Vec<Employee>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Vec<Employee>) - every employee, empty when there are none
+    ///  * on fail:
+    ///    * ApplicationError - the relevant Application error
+    ///
+    pub fn get_employees(&mut self) -> Result<Vec<Employee>, ApplicationError> {
+        let mut employees = self.database.get_employees()?;
+        employees.sort_by_key(|employee| employee.get_employee_id());
+        for employee in &employees {
+            self.stored_hashes.insert(
+                employee.get_employee_id(),
+                employee.get_employee_hash().to_string(),
+            );
+            self.stored_employees
+                .insert(employee.get_employee_id(), employee.clone());
+        }
+        Ok(employees)
+    }
+
     /// Add a new employee object to storage
     ///
     /// adds a new employee object to both the local storage
@@ -193,58 +288,284 @@ impl EmployeeHandler {
     /// # Arguments
     ///
     /// * `&mut self` - mutable reference to the employeehandler instance
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `employee: &Employee` - the employee object to add to storage
     ///
     ///
     ///# Returns
     ///
-    ///* 'Result<(), ApplicationError> ' -
-    ///     on success:
-    ///         Ok(()) - ok status, the employee operation was successful
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
+    ///* 'Result<i32, ApplicationError> ' -
+    ///  * on success:
+    ///    * Ok(i32) - the employee_id the database assigned to the new employee
+    ///  * on fail:
+    ///    * ApplicationError::PermissionError - the signed in employee is not an admin
+    ///    * ApplicationError - the relevant Application error
     ///         
-    pub fn add_new_employee(&mut self, employee: &Employee) -> Result<(), ApplicationError> {
-        let transaction = Transaction::new(&mut self.database)?;
-        transaction.db.new_employee(employee)?;
-        self.stored_hashes.insert(
-            employee.get_employee_id(),
-            employee.get_employee_hash().to_string(),
-        );
-        self.stored_employees
-            .insert(employee.get_employee_id(), employee.clone());
+    pub fn add_new_employee(
+        &mut self,
+        session: &Session,
+        employee: &Employee,
+    ) -> Result<i32, ApplicationError> {
+        Self::authorize_admin(session)?;
+        let mut transaction = Transaction::new(&mut self.database)?;
+        let employee_id = transaction.db.new_employee(employee)?;
+        // store the persisted id, the caller's id value is ignored by the database
+        let mut employee = employee.clone();
+        employee.set_employee_id(employee_id);
+        transaction.db.new_audit_entry(&AuditEntry::new(
+            session,
+            AuditAction::AddEmployee,
+            employee_id,
+            None,
+            Some(employee_snapshot(&employee, false)),
+        ))?;
         transaction.commit()?;
-        Ok(()) // ok status returned on success
+        // the local stored employees only hold employees the database has
+        self.stored_hashes
+            .insert(employee_id, employee.get_employee_hash().to_string());
+        self.stored_employees.insert(employee_id, employee);
+        Ok(employee_id) // ok status returned on success
+    }
+
+    /// returns true when at least one employee has the admin role
+    pub fn has_admin(&mut self) -> Result<bool, ApplicationError> {
+        Ok(self
+            .get_employees()?
+            .iter()
+            .any(|employee| employee.get_employee_role() == EmployeeRole::Admin))
+    }
+
+    /// Adds the first admin, without a signed in employee
+    ///
+    /// a new database has no employees, so no one can sign in to add
+    /// them. Until an admin exists, one may be added without a Session,
+    /// e.g. with `employee add --role admin` on the command line. The
+    /// audit entry is attributed to the new admin.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` - mutable reference to the employeehandler instance
+    /// * `employee: &Employee` - the admin to add
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<i32, ApplicationError>' - the employee_id the database assigned to the admin
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::PermissionError' - an admin already exists, or the
+    ///  employee is not an admin
+    ///* 'ApplicationError' - the relevant Application error
+    ///
+    pub fn bootstrap_admin(&mut self, employee: &Employee) -> Result<i32, ApplicationError> {
+        if employee.get_employee_role() != EmployeeRole::Admin {
+            return Err(ApplicationError::PermissionError(
+                "Only an admin may be added without signing in".to_string(),
+            ));
+        }
+        if self.has_admin()? {
+            return Err(ApplicationError::PermissionError(
+                "An admin already exists, sign in as an admin to add employees".to_string(),
+            ));
+        }
+        let mut transaction = Transaction::new(&mut self.database)?;
+        let employee_id = transaction.db.new_employee(employee)?;
+        let mut employee = employee.clone();
+        employee.set_employee_id(employee_id);
+        transaction.db.new_audit_entry(&AuditEntry::by_employee(
+            employee_id,
+            AuditAction::AddEmployee,
+            employee_id,
+            None,
+            Some(employee_snapshot(&employee, false)),
+        ))?;
+        transaction.commit()?;
+        self.stored_hashes
+            .insert(employee_id, employee.get_employee_hash().to_string());
+        self.stored_employees.insert(employee_id, employee);
+        Ok(employee_id)
     }
 
     ///function to modify the details of an employee
     ///
     ///updates/modifies an existing employee object in both
-    ///local, and remote storage structures
+    ///local, and remote storage structures. Only admins may modify
+    ///employees, see change_own_password for an employee's own password.
     ///
     ///# Arguments
     ///
     /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `employee: &Employee` -Reference to a specific Employee object
     ///
     ///# Returns
     ///
     ///* 'Result<(), ApplicationError> ' -
-    ///     on success:
-    ///         Ok(()) - ok status, the employee operation was successful
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
+    ///  * on success:
+    ///    * Ok(()) - ok status, the employee operation was successful
+    ///  * on fail:
+    ///    * ApplicationError::PermissionError - the signed in employee is not an admin,
+    ///      or would remove their own admin role
+    ///    * ApplicationError - the relevant Application error
     ///         
-    pub fn modify_employee(&mut self, employee: &Employee) -> Result<(), ApplicationError> {
-        let transaction = Transaction::new(&mut self.database)?;
+    pub fn modify_employee(
+        &mut self,
+        session: &Session,
+        employee: &Employee,
+    ) -> Result<(), ApplicationError> {
+        Self::authorize_admin(session)?;
+        let previous = self
+            .get_employee(employee.get_employee_id())?
+            .ok_or_else(|| {
+                ApplicationError::NoMatchError(format!(
+                    "No employee found with the ID: {}",
+                    employee.get_employee_id()
+                ))
+            })?;
+        // an admin demoting themselves could leave the firm without an admin
+        if employee.get_employee_id() == session.get_employee_id()
+            && employee.get_employee_role() != previous.get_employee_role()
+            && !employee.get_employee_role().can_manage_employees()
+        {
+            return Err(ApplicationError::PermissionError(
+                "Admins cannot remove their own admin role".to_string(),
+            ));
+        }
+        self.save_employee(
+            employee,
+            AuditEntry::new(
+                session,
+                AuditAction::ModifyEmployee,
+                employee.get_employee_id(),
+                Some(employee_snapshot(&previous, false)),
+                Some(employee_snapshot(
+                    employee,
+                    previous.get_employee_hash() != employee.get_employee_hash(),
+                )),
+            ),
+        )
+    }
+
+    ///function used by an employee to change their own password
+    ///
+    ///verifies the employee's current password, then replaces it with
+    ///the new password, which must meet the PasswordPolicy.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `session: &Session` - the signed in employee, whose password is changed
+    /// * `current_password: &str` - the employee's current password
+    /// * `new_password: &str` - the new password
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError> ' - Ok(()) once the password was changed
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::PassValidationError' - the current password is incorrect
+    ///* 'ApplicationError::InputError' - the new password does not meet the
+    ///  PasswordPolicy, or matches the current password
+    ///* 'ApplicationError' - the relevant Application error
+    ///
+    pub fn change_own_password(
+        &mut self,
+        session: &Session,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<(), ApplicationError> {
+        let employee_id = session.get_employee_id();
+        let previous = self.get_employee(employee_id)?.ok_or_else(|| {
+            ApplicationError::NoMatchError(format!("No employee found with the ID: {}", employee_id))
+        })?;
+        if !Authenticator::verify_password(previous.get_employee_hash(), current_password) {
+            return Err(ApplicationError::PassValidationError(
+                "The current password is incorrect".to_string(),
+            ));
+        }
+        if new_password == current_password {
+            return Err(ApplicationError::InputError(
+                "The new password must differ from the current password".to_string(),
+            ));
+        }
+        PasswordPolicy::current().validate(new_password)?;
+
+        let mut employee = previous.clone();
+        employee.change_password(new_password)?;
+        self.save_employee(
+            &employee,
+            AuditEntry::new(
+                session,
+                AuditAction::ChangePassword,
+                employee_id,
+                Some(employee_snapshot(&previous, false)),
+                Some(employee_snapshot(&employee, true)),
+            ),
+        )
+    }
+
+    ///function used by the Authenticator to replace an employee's password hash
+    ///
+    ///saves a new hash of the employee's already verified password, when the
+    ///stored hash is weaker than the HashPolicy. Not permission checked, it
+    ///is only called for the employee who just signed in.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `employee_id: i32` - employee_id of the authenticated employee
+    /// * `hash: &str` - the new, encoded password hash
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError> ' - Ok(()) once the hash was replaced
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::NoMatchError' - no employee has the employee_id
+    ///* 'ApplicationError' - the relevant Application error
+    ///
+    pub fn update_password_hash(
+        &mut self,
+        employee_id: i32,
+        hash: &str,
+    ) -> Result<(), ApplicationError> {
+        let previous = self.get_employee(employee_id)?.ok_or_else(|| {
+            ApplicationError::NoMatchError(format!("No employee found with the ID: {}", employee_id))
+        })?;
+        let employee = Employee::from_stored(
+            employee_id,
+            previous.get_employee_name(),
+            hash,
+            previous.get_employee_role(),
+        );
+        // the employee's own sign in made the change
+        self.save_employee(
+            &employee,
+            AuditEntry::by_employee(
+                employee_id,
+                AuditAction::RehashPassword,
+                employee_id,
+                Some(employee_snapshot(&previous, false)),
+                Some(employee_snapshot(&employee, true)),
+            ),
+        )
+    }
+
+    // saves a modified employee & its audit entry in one transaction, then
+    // updates the local stored employee and stored hashes hashmaps
+    fn save_employee(&mut self, employee: &Employee, entry: AuditEntry) -> Result<(), ApplicationError> {
+        let mut transaction = Transaction::new(&mut self.database)?;
         transaction.db.update_employee(employee)?;
+        transaction.db.new_audit_entry(&entry)?;
+        transaction.commit()?;
         self.stored_hashes.insert(
             employee.get_employee_id(),
             employee.get_employee_hash().to_string(),
         );
         self.stored_employees
             .insert(employee.get_employee_id(), employee.clone());
-        transaction.commit()?;
         Ok(()) // ok status returned on success
     }
 
@@ -256,43 +577,225 @@ impl EmployeeHandler {
     ///# Arguments
     ///
     /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `employee_id: i32` - employee_id that corresponds to the matching employee
+    /// * `clients: &ClientHandler` - the client handler, used to check the employee
+    ///   has no clients assigned
     ///
     ///# Returns
     ///
     ///     on success:
     ///         Ok(()) - ok status, the employee operation was successful
     ///     on fail:
+    ///         ApplicationError::PermissionError - the signed in employee is not an admin, or
+    ///             would delete their own account
+    ///         ApplicationError::InputError - the employee still has clients assigned
     ///         ApplicationError - the relevant Application error
     ///
-    pub fn delete_employee(&mut self, employee_id: i32) -> Result<(), ApplicationError> {
-        let transaction = Transaction::new(&mut self.database)?;
+    pub fn delete_employee(
+        &mut self,
+        session: &Session,
+        employee_id: i32,
+        clients: &ClientHandler,
+    ) -> Result<(), ApplicationError> {
+        Self::authorize_delete(session, employee_id, clients)?;
+        Self::delete_employee_in(&mut self.database, session, employee_id)?;
+        self.stored_hashes.remove(&employee_id);
+        self.stored_employees.remove(&employee_id);
+        Ok(()) // ok status returned on success
+    }
+
+    // checks that the signed in employee may delete the employee, and that the
+    // employee has no clients left, their clients would otherwise be paired
+    // with a missing employee
+    fn authorize_delete(
+        session: &Session,
+        employee_id: i32,
+        clients: &ClientHandler,
+    ) -> Result<(), ApplicationError> {
+        Self::authorize_admin(session)?;
+        if employee_id == session.get_employee_id() {
+            return Err(ApplicationError::PermissionError(
+                "Employees cannot delete their own account".to_string(),
+            ));
+        }
+        if let Some(client_ids) = clients.get_clients_for_employee(employee_id) {
+            if !client_ids.is_empty() {
+                return Err(ApplicationError::InputError(format!(
+                    "Employee: {} still has {} client(s) assigned, reassign them first",
+                    employee_id,
+                    client_ids.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    // deletes the employee & their login attempts, with its audit entry, through
+    // the given database handle. Joins the handle's transaction when one is open,
+    // the local stored employees are left to the caller, once it has committed
+    fn delete_employee_in(
+        database: &mut Box<dyn DatabaseManager>,
+        session: &Session,
+        employee_id: i32,
+    ) -> Result<(), ApplicationError> {
+        let mut transaction = Transaction::new(database)?;
+        // the audit entry records the employee as the database holds it, the
+        // stored employee may be out of date when another session changed it
+        let previous = transaction.db.get_employee(employee_id)?;
         transaction.db.remove_employee(employee_id)?;
+        transaction.db.clear_login_attempts(employee_id)?;
+        transaction.db.new_audit_entry(&AuditEntry::new(
+            session,
+            AuditAction::DeleteEmployee,
+            employee_id,
+            previous.map(|employee| employee_snapshot(&employee, false)),
+            None,
+        ))?;
+        transaction.commit()
+    }
+
+    ///function used to reassign an employee's clients, then delete the employee
+    ///
+    ///reassigns every client of the employee to another employee, and
+    ///deletes the employee, all in one transaction (ClientHandler::batch).
+    ///Either every change is committed, or none are.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
+    /// * `employee_id: i32` - employee_id of the employee to delete
+    /// * `new_employee_id: i32` - employee_id of the employee to reassign the clients to
+    /// * `clients: &mut ClientHandler` - the client handler holding the employee's clients
+    ///
+    ///# Returns
+    ///
+    ///     on success:
+    ///         Ok(usize) - the number of clients reassigned
+    ///     on fail:
+    ///         ApplicationError::PermissionError - the signed in employee is not an admin, or
+    ///             would delete their own account
+    ///         ApplicationError::InputError - the clients would be reassigned to the
+    ///             employee being deleted
+    ///         ApplicationError - the relevant Application error, nothing was changed
+    ///
+    pub fn reassign_and_delete_employee(
+        &mut self,
+        session: &Session,
+        employee_id: i32,
+        new_employee_id: i32,
+        clients: &mut ClientHandler,
+    ) -> Result<usize, ApplicationError> {
+        Self::authorize_admin(session)?;
+        if new_employee_id == employee_id {
+            return Err(ApplicationError::InputError(
+                "Clients must be reassigned to a different employee".to_string(),
+            ));
+        }
+        let client_ids = clients
+            .get_clients_for_employee(employee_id)
+            .cloned()
+            .unwrap_or_default();
+        clients.batch(|clients| {
+            for &client_id in &client_ids {
+                let mut client = clients.get_client(client_id)?.clone();
+                client.change_client_employee_pair(new_employee_id);
+                clients.update_client(session, &client)?;
+            }
+            Self::authorize_delete(session, employee_id, clients)?;
+            // delete through the batch's transaction handle, so the deletion joins it
+            Self::delete_employee_in(&mut clients.database, session, employee_id)
+        })?;
         self.stored_hashes.remove(&employee_id);
         self.stored_employees.remove(&employee_id);
+        Ok(client_ids.len())
+    }
+
+    ///function used to unlock an employee's login
+    ///
+    ///clears the employee's failed login record, ending any lockout.
+    ///
+    ///# Arguments
+    ///
+    /// * `&mut self` -mutable reference to self(EmployeeManager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
+    /// * `employee_id: i32` - employee_id of the employee to unlock
+    ///
+    ///# Returns
+    ///
+    ///     on success:
+    ///         Ok(Some(LoginAttempts)) - the cleared failed login record
+    ///         Ok(None) - the employee had no failed attempts recorded, nothing changed
+    ///     on fail:
+    ///         ApplicationError::PermissionError - the signed in employee is not an admin
+    ///         ApplicationError - the relevant Application error
+    ///
+    pub fn unlock_employee(
+        &mut self,
+        session: &Session,
+        employee_id: i32,
+    ) -> Result<Option<LoginAttempts>, ApplicationError> {
+        Self::authorize_admin(session)?;
+        let previous = match self.database.get_login_attempts(employee_id)? {
+            Some(attempts) => attempts,
+            None => return Ok(None),
+        };
+        let mut transaction = Transaction::new(&mut self.database)?;
+        transaction.db.clear_login_attempts(employee_id)?;
+        transaction.db.new_audit_entry(&AuditEntry::new(
+            session,
+            AuditAction::UnlockEmployee,
+            employee_id,
+            Some(login_attempts_snapshot(&previous)),
+            None,
+        ))?;
         transaction.commit()?;
-        Ok(()) // ok status returned on success
+        Ok(Some(previous))
+    }
+
+    /// checks that an employee may manage other employees
+    ///
+    /// only admins may add, modify, or delete employees.
+    ///
+    ///# Arguments
+    ///
+    /// * `session: &Session` - the signed in employee performing the operation
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok when permitted,
+    ///  otherwise ApplicationError::PermissionError
+    ///
+    pub fn authorize_admin(session: &Session) -> Result<(), ApplicationError> {
+        if session.get_role().can_manage_employees() {
+            Ok(())
+        } else {
+            Err(ApplicationError::PermissionError(format!(
+                "Employee: {} is an {}, only admins may manage employees",
+                session.get_employee_id(),
+                session.get_role().name()
+            )))
+        }
     }
 }
 
+/// the number of clients displayed per page when listing every client
+pub const CLIENT_PAGE_SIZE: usize = 20;
+
 /// Client handler represented here.
 ///
 /// Manages / handles / delegates all client related operations
 ///
 ///# Fields
 ///
-///* `local_avl_tree: AVLTree<Client>` - The primary local data storage object, an AVL tree of  Clients
+///* `client_cache: ClientCache` - The primary local data storage object, an AVL tree of Clients,
+///  with indexes of the clients by assigned employee, service and name
 ///* `database: Box<dyn DatabaseManager>` - box containing DatabaseManager implementation of db
-///* `employee_client_pairs: HashMap<i32, Vec<i32>>` - hashmap containing employe id keys /
-///         a value vector of the clients they have assigned to them
 ///
 pub struct ClientHandler {
-    /// the local avltree built from clients in database
-    local_avl_tree: AVLTree<Client>,
-
-    /// local hashmap for O(1) employee / client pairings
-    /// uses int <asn_employee_id> key, value is vector of int <client_id>s
-    employee_client_pairs: HashMap<i32, Vec<i32>>,
+    /// the local avltree & indexes built from clients in database
+    client_cache: ClientCache,
 
     /// smart pointer to databaseManager
     database: Box<dyn DatabaseManager>,
@@ -300,7 +803,6 @@ pub struct ClientHandler {
 
 /// https://doc.rust-lang.org/book/ch03-04-comments.html
 /// https://doc.rust-lang.org/rust-by-example/meta/doc.html
-
 impl ClientHandler {
     /// constructor for newclienthManager instance
     ///
@@ -323,22 +825,84 @@ impl ClientHandler {
     ///             transactions
     ///
     pub fn new(database: Box<dyn DatabaseManager>) -> Result<Self, ApplicationError> {
-        let clients = database.get_clients().map_err(ApplicationError::from)?; // clients is the vector containing clients, or err
-        let mut local_avltree = AVLTree::new();
-        let mut employee_client_pairs = HashMap::new();
+        let mut client_handler = Self {
+            client_cache: ClientCache::new(),
+            database,
+        };
+        client_handler.reload()?;
+        Ok(client_handler)
+    }
+
+    /// rebuilds the local structures from the database
+    ///
+    /// clears the local avl tree and employee / client pairings, then
+    /// repopulates both with every client currently in the database.
+    /// Used on construction, and to resync after a rolled back batch.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to self(ClientManager instance)
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(()) - local structures now match the database
+    ///  * on fail:
+    ///    * ApplicationError - failure to retrieve or insert the clients
+    ///
+    pub fn reload(&mut self) -> Result<(), ApplicationError> {
+        let clients = self.database.get_clients().map_err(ApplicationError::from)?; // clients is the vector containing clients, or err
+        self.client_cache.clear();
         for client in clients {
-            employee_client_pairs
-                .entry(client.get_asn_employee())
-                .or_insert_with(Vec::new)
-                .push(client.get_client_id());
-            local_avltree.insert(client)?; // call insert method on each client
+            self.client_cache.insert(client)?; // call insert method on each client
         }
+        Ok(())
+    }
 
-        Ok(Self {
-            local_avl_tree: local_avltree,
-            database,
-            employee_client_pairs,
-        })
+    /// runs several client operations inside one database transaction
+    ///
+    /// begins a transaction, then calls the provided closure with this
+    /// handler. Operations made by the closure (new_client, update_client,
+    /// remove_client) join the open transaction instead of starting their own.
+    /// All of them are committed together when the closure returns Ok,
+    /// otherwise all are rolled back, and the local structures are reloaded
+    /// from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `&mut self` - mutable reference to self(ClientManager instance)
+    /// * `operations: F` - closure performing the batched operations
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(()) - every operation was committed
+    ///  * on fail:
+    ///    * ApplicationError - the error from the closure, or from the
+    ///      transaction itself. Nothing was committed.
+    ///
+    pub fn batch<F>(&mut self, operations: F) -> Result<(), ApplicationError>
+    where
+        F: FnOnce(&mut ClientHandler) -> Result<(), ApplicationError>,
+    {
+        // the operations run with the transaction handle in place of the database,
+        // so each of their Transactions joins it
+        let handle = self.database.begin_transaction()?;
+        let database = std::mem::replace(&mut self.database, handle);
+        let result = operations(self);
+        let mut handle = std::mem::replace(&mut self.database, database);
+        let result = result.and_then(|_| handle.commit_transaction().map_err(ApplicationError::from));
+
+        if result.is_err() {
+            if handle.in_transaction() {
+                let _ = handle.rollback_transaction();
+            }
+            // local structures may hold changes that never reached the database
+            self.reload()?;
+        }
+        result
     }
 
     ///single client retrieval method
@@ -360,7 +924,41 @@ impl ClientHandler {
     ///         ApplicationError - the relevant Application error such as NoMatchFound
     ///    
     pub fn get_client(&self, id: i32) -> Result<&Client, ApplicationError> {
-        self.local_avl_tree.find(id)
+        self.client_cache.get(id)
+    }
+
+    /// client count retrieval method
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    ///
+    ///# Returns
+    ///
+    ///* 'usize' - the number of clients in the local avl tree
+    ///
+    pub fn client_count(&self) -> usize {
+        self.client_cache.len()
+    }
+
+    /// paginated client list retrieval method
+    ///
+    /// returns a page of every client in the firm, in client id order,
+    /// using a range query on the local avl tree. The next page starts
+    /// after the id of the last client of this page.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    /// * `after: Option<i32>` - the last client id of the previous page, None for the first page
+    /// * `limit: usize` - the maximum number of clients on the page
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<&Client>' - the clients of the page, empty when there are no more clients
+    ///
+    pub fn get_clients_page(&self, after: Option<i32>, limit: usize) -> Vec<&Client> {
+        self.client_cache.page(after, limit)
     }
 
     /// client list by employee pair retrieval method
@@ -375,15 +973,152 @@ impl ClientHandler {
     ///
     ///# Returns
     ///
-    ///* 'Option<&Vec<i32>>' -
-    ///     on success:
-    ///         Ok(()) - Ok status, and the vector containing all clients that are assigned
+    ///* 'Option<&BTreeSet<i32>>' -
+    ///  * if Some - the sorted ids of all clients that are assigned
     ///                 to a particular employee
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
+    ///  * if None - the employee has no clients assigned
     ///    
-    pub fn get_clients_for_employee(&self, employee_id: i32) -> Option<&Vec<i32>> {
-        self.employee_client_pairs.get(&employee_id)
+    pub fn get_clients_for_employee(&self, employee_id: i32) -> Option<&BTreeSet<i32>> {
+        self.client_cache.clients_for_employee(employee_id)
+    }
+
+    /// paired employee id retrieval method
+    ///
+    /// returns the id of every employee that currently has at least
+    /// one client assigned to them, in ascending order.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<i32>' - sorted employee ids, empty when there are no clients
+    ///
+    pub fn get_paired_employee_ids(&self) -> Vec<i32> {
+        self.client_cache.employee_ids()
+    }
+
+    /// client search method
+    ///
+    /// searches the local clients by assigned employee, service and / or
+    /// name using the client cache indexes, e.g. every retirement client
+    /// of employee 5.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    /// * `filter: &ClientFilter` - the search, unset fields match any client
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<&Client>' - the matching clients in client id order, empty when none match
+    ///
+    pub fn find_clients(&self, filter: &ClientFilter) -> Vec<&Client> {
+        self.client_cache.find(filter)
+    }
+
+    /// client name search method
+    ///
+    /// searches the local clients by name, for employees who know a
+    /// client's name but not their id. Matches exact names, the start of
+    /// the name or one of its words, parts of the name, and names within
+    /// a few typos, case insensitive.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    /// * `search: &str` - the name, or part of it, as entered
+    /// * `limit: usize` - the maximum number of matches returned
+    ///
+    ///# Returns
+    ///
+    ///* 'Vec<ClientMatch>' - the matches ranked best first, empty when none match
+    ///
+    pub fn search_clients_by_name(&self, search: &str, limit: usize) -> Vec<ClientMatch<'_>> {
+        self.client_cache.search_by_name(search, limit)
+    }
+
+    /// checks that an employee may modify an existing client
+    ///
+    /// supervisors and admins may modify any client. Advisors may only
+    /// modify the clients assigned to them in the client cache.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
+    /// * `client_id: i32` - id of the client being modified
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok when permitted,
+    ///  otherwise ApplicationError::PermissionError
+    ///
+    pub fn authorize_client(&self, session: &Session, client_id: i32) -> Result<(), ApplicationError> {
+        let owns_client = self
+            .get_clients_for_employee(session.get_employee_id())
+            .is_some_and(|client_ids| client_ids.contains(&client_id));
+        if owns_client || session.get_role().can_modify_any_client() {
+            Ok(())
+        } else {
+            Err(ApplicationError::PermissionError(format!(
+                "Client: {} is not assigned to Employee: {}, advisors may only modify their own clients",
+                client_id,
+                session.get_employee_id()
+            )))
+        }
+    }
+
+    /// client history search method
+    ///
+    /// searches the recorded reassignments & service changes of clients.
+    /// Only supervisors and admins may review the client history.
+    ///
+    ///# Arguments
+    ///
+    /// * `&self` - reference to self (ClientManager instance)
+    /// * `session: &Session` - the signed in employee performing the search
+    /// * `filter: &HistoryFilter` - the search, unset fields match any entry
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<Vec<ClientHistoryEntry>, ApplicationError>' -
+    ///  * on success:
+    ///    * Ok(Vec<ClientHistoryEntry>) - matching entries, oldest first
+    ///  * on fail:
+    ///    * ApplicationError::PermissionError - the signed in employee is an advisor
+    ///    * ApplicationError - the relevant Application error
+    ///
+    pub fn get_client_history(
+        &self,
+        session: &Session,
+        filter: &HistoryFilter,
+    ) -> Result<Vec<ClientHistoryEntry>, ApplicationError> {
+        Self::authorize_history(session)?;
+        Ok(self.database.get_client_history(filter)?)
+    }
+
+    /// checks that an employee may review the client history
+    ///
+    ///# Arguments
+    ///
+    /// * `session: &Session` - the signed in employee performing the search
+    ///
+    ///# Returns
+    ///
+    ///* 'Result<(), ApplicationError>' - Ok for supervisors and admins,
+    ///  otherwise ApplicationError::PermissionError
+    ///
+    pub fn authorize_history(session: &Session) -> Result<(), ApplicationError> {
+        if session.get_role().can_modify_any_client() {
+            Ok(())
+        } else {
+            Err(ApplicationError::PermissionError(format!(
+                "Employee: {} is an advisor, only supervisors and admins may review client history",
+                session.get_employee_id()
+            )))
+        }
     }
 
     /// Updating an existing client in the database, and in local storage.
@@ -394,54 +1129,84 @@ impl ClientHandler {
     ///# Arguments
     ///
     /// * `&mut self` - mutable reference to self(ClientMAnager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `client: &Client` - Reference to a specific Client object
     ///
     ///# Returns
     ///
-    ///* 'Result<(), ApplicationError> ' -
-    ///     on success:
-    ///         Ok(()) -
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
-    ///    
-    pub fn update_client(&mut self, client: &Client) -> Result<(), ApplicationError> {
-        // First, check if the assigned employee has changed
-        let old_employee_id = {
-            let old_client = self.get_client(client.get_client_id())?;
-            old_client.get_asn_employee()
-        };
-
-        let employee_changed = old_employee_id != client.get_asn_employee();
+    ///* 'Result<(), ApplicationError>' - Ok when the client was updated
+    ///
+    ///# Errors
+    ///
+    ///* 'ApplicationError::PermissionError' - an advisor modifying another employee's client
+    ///* 'ApplicationError::NoMatchError' - the client is no longer in the database
+    ///* 'ApplicationError' - the relevant Application error
+    ///
+    pub fn update_client(&mut self, session: &Session, client: &Client) -> Result<(), ApplicationError> {
+        self.authorize_client(session, client.get_client_id())?;
 
         // Update the database first
         {
-            let transaction = Transaction::new(&mut self.database)?;
+            let mut transaction = Transaction::new(&mut self.database)?;
+            // the previous values are read from the database within the transaction,
+            // the cached client may be out of date when another session changed it
+            let old_client = transaction
+                .db
+                .get_client(client.get_client_id())?
+                .ok_or_else(|| {
+                    ApplicationError::NoMatchError(format!(
+                        "No matching client found with ID value: {}",
+                        client.get_client_id()
+                    ))
+                })?;
+            if !session.get_role().can_modify_any_client()
+                && old_client.get_asn_employee() != session.get_employee_id()
+            {
+                return Err(ApplicationError::PermissionError(format!(
+                    "Client: {} is no longer assigned to Employee: {}",
+                    client.get_client_id(),
+                    session.get_employee_id()
+                )));
+            }
+            let old_employee_id = old_client.get_asn_employee();
+            let employee_changed = old_employee_id != client.get_asn_employee();
+            let service_changed = old_client.get_client_service() != client.get_client_service();
+
             transaction
                 .db
                 .update_client(client)
                 .map_err(ApplicationError::from)?;
-            transaction.commit()?;
-        }
-
-        // Now update local structures
-        if employee_changed {
-            // Remove from old employee's list
-            if let Some(client_list) = self.employee_client_pairs.get_mut(&old_employee_id) {
-                client_list.retain(|&id| id != client.get_client_id());
-                if client_list.is_empty() {
-                    self.employee_client_pairs.remove(&old_employee_id);
-                }
+            transaction.db.new_audit_entry(&AuditEntry::new(
+                session,
+                AuditAction::UpdateClient,
+                client.get_client_id(),
+                Some(client_snapshot(&old_client)),
+                Some(client_snapshot(client)),
+            ))?;
+            // reassignments & service changes are also kept in the client history
+            if employee_changed {
+                transaction.db.new_history_entry(&ClientHistoryEntry::new(
+                    session,
+                    client.get_client_id(),
+                    HistoryAction::Reassign,
+                    old_employee_id,
+                    client.get_asn_employee(),
+                ))?;
             }
-
-            // Add to new employee's list
-            self.employee_client_pairs
-                .entry(client.get_asn_employee())
-                .or_insert_with(Vec::new)
-                .push(client.get_client_id());
+            if service_changed {
+                transaction.db.new_history_entry(&ClientHistoryEntry::new(
+                    session,
+                    client.get_client_id(),
+                    HistoryAction::ServiceChange,
+                    old_client.get_client_service(),
+                    client.get_client_service(),
+                ))?;
+            }
+            transaction.commit()?;
         }
 
-        self.local_avl_tree.remove(client.get_client_id())?;
-        self.local_avl_tree.insert(client.clone())?;
+        // Now update local structures, in place, the indexes follow the changed values
+        self.client_cache.update(client.clone())?;
 
         Ok(())
     }
@@ -454,30 +1219,45 @@ impl ClientHandler {
     ///# Arguments
     ///
     /// * `&mut self` - mutable reference to self(ClientMAnager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `client: &Client` - Reference to a specific Client object
     ///
     ///# Returns
     ///
-    ///* 'Result<(), ApplicationError> ' -
-    ///     on success:
-    ///         Ok(()) -
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
+    ///* 'Result<i32, ApplicationError> ' -
+    ///  * on success:
+    ///    * Ok(i32) - the client id of the new client
+    ///  * on fail:
+    ///    * ApplicationError::PermissionError - an advisor adding a client for another employee
+    ///    * ApplicationError - the relevant Application error
     ///
-    pub fn new_client(&mut self, client: &Client) -> Result<(), ApplicationError> {
-        let transaction = Transaction::new(&mut self.database)?;
-        transaction.db.new_client(client)?;
-        self.local_avl_tree.insert(client.clone())?;
-
-        // add new client object to employee_client_pairs hashmap
-        self.employee_client_pairs
-            .entry(client.get_asn_employee())
-            .or_insert_with(Vec::new)
-            .push(client.get_client_id());
-
+    pub fn new_client(&mut self, session: &Session, client: &Client) -> Result<i32, ApplicationError> {
+        if !session.get_role().can_modify_any_client()
+            && client.get_asn_employee() != session.get_employee_id()
+        {
+            return Err(ApplicationError::PermissionError(format!(
+                "Advisors may only add clients assigned to themselves, not to Employee: {}",
+                client.get_asn_employee()
+            )));
+        }
+        let mut transaction = Transaction::new(&mut self.database)?;
+        let client_id = transaction.db.new_client(client)?;
+        // store the persisted id, the caller's id value is ignored by the database
+        let mut client = client.clone();
+        client.set_client_id(client_id);
+        transaction.db.new_audit_entry(&AuditEntry::new(
+            session,
+            AuditAction::NewClient,
+            client_id,
+            None,
+            Some(client_snapshot(&client)),
+        ))?;
         transaction.commit()?;
 
-        Ok(())
+        // add new client object to the tree & indexes, once the database has it
+        self.client_cache.insert(client)?;
+
+        Ok(client_id)
     }
     ///removes a client object from data storage
     ///
@@ -488,36 +1268,34 @@ impl ClientHandler {
     ///# Arguments
     ///
     /// * `&mut self` - mutable reference to self(ClientMAnager instance)
+    /// * `session: &Session` - the signed in employee performing the operation
     /// * `client: &Client` - Reference to a specific Client object
     ///
     ///# Returns
     ///
     ///* 'Result<(), ApplicationError> ' -
-    ///     on success:
-    ///         Ok(()) -
-    ///     on fail:
-    ///         ApplicationError - the relevant Application error
-    ///
-    pub fn remove_client(&mut self, client: &Client) -> Result<(), ApplicationError> {
-        let transaction = Transaction::new(&mut self.database)?;
+    ///  * on success:
+    ///    * Ok(()) -
+    ///  * on fail:
+    ///    * ApplicationError::PermissionError - an advisor removing another employee's client
+    ///    * ApplicationError - the relevant Application error
+    ///
+    pub fn remove_client(&mut self, session: &Session, client: &Client) -> Result<(), ApplicationError> {
+        self.authorize_client(session, client.get_client_id())?;
+        let mut transaction = Transaction::new(&mut self.database)?;
         transaction.db.remove_client(client)?;
-
-        // attempts to remove a client from their employee pairing
-        if let Some(client_list) = self
-            .employee_client_pairs
-            .get_mut(&client.get_asn_employee())
-        {
-            client_list.retain(|&id| id != client.get_client_id());
-            // employee has no clients? remove id from hashmap keys, to prevent empty list returns
-            if client_list.is_empty() {
-                self.employee_client_pairs
-                    .remove(&client.get_asn_employee());
-            }
-        }
-
-        self.local_avl_tree.remove(client.get_client_id())?;
-
+        transaction.db.new_audit_entry(&AuditEntry::new(
+            session,
+            AuditAction::RemoveClient,
+            client.get_client_id(),
+            Some(client_snapshot(client)),
+            None,
+        ))?;
         transaction.commit()?;
+
+        // removes the client from the tree & indexes, including their employee pairing,
+        // once it is removed from the database
+        self.client_cache.remove(client.get_client_id())?;
         Ok(())
     }
 }
@@ -536,20 +1314,55 @@ impl ClientHandler {
 /// The Transaction struct is created to help manage consistency of operations
 /// and data between the database and local data strucures / storage.
 ///
+/// Beginning the transaction opens a transaction handle on the wrapped
+/// DatabaseManager (begin_transaction), which the Transaction owns. Every query
+/// made through `db` runs on that handle until the transaction is committed,
+/// or rolled back on drop. The wrapped DatabaseManager itself is unchanged.
 ///
 ///# Fields
 ///
-///* `db: &'a mut Box<dyn DatabaseManager>` - mutable ref to boxed DatabaseManager
-///     database implementation.
+///* `db: TransactionDb<'a>` - the transaction handle queries are made through
 ///
 ///* `completed: bool` - holds the status of the transaction
 ///
-///
 pub struct Transaction<'a> {
-    db: &'a mut Box<dyn DatabaseManager>,
+    db: TransactionDb<'a>,
     completed: bool,
 }
 
+/// Represents the database handle a Transaction runs its queries on
+///
+///# Variants
+///
+///* `Owned` - the transaction handle this Transaction began, and commits or rolls back
+///* `Joined` - a transaction handle that was already open (ClientHandler::batch),
+///  the outer transaction then decides whether to commit or roll back.
+///
+enum TransactionDb<'a> {
+    Owned(Box<dyn DatabaseManager>),
+    Joined(&'a mut Box<dyn DatabaseManager>),
+}
+
+impl<'a> Deref for TransactionDb<'a> {
+    type Target = Box<dyn DatabaseManager>;
+
+    fn deref(&self) -> &Box<dyn DatabaseManager> {
+        match *self {
+            TransactionDb::Owned(ref db) => db,
+            TransactionDb::Joined(ref db) => db,
+        }
+    }
+}
+
+impl<'a> DerefMut for TransactionDb<'a> {
+    fn deref_mut(&mut self) -> &mut Box<dyn DatabaseManager> {
+        match *self {
+            TransactionDb::Owned(ref mut db) => db,
+            TransactionDb::Joined(ref mut db) => db,
+        }
+    }
+}
+
 impl<'a> Transaction<'a> {
     /// constructor function for Transaction
     ///
@@ -568,9 +1381,14 @@ impl<'a> Transaction<'a> {
     ///         Ok(()) - ok status + new transaction
     ///     on fail:
     ///         ApplicationError - returned upon failure to generate new transaction
-    ///    
+    ///
     pub fn new(db: &'a mut Box<dyn DatabaseManager>) -> Result<Self, ApplicationError> {
-        db.begin_transaction()?;
+        // join an already open transaction handle, rather than failing to begin one
+        let db = if db.in_transaction() {
+            TransactionDb::Joined(db)
+        } else {
+            TransactionDb::Owned(db.begin_transaction()?)
+        };
         Ok(Transaction {
             db,
             completed: false,
@@ -583,7 +1401,7 @@ impl<'a> Transaction<'a> {
     ///
     ///# Arguments
     ///
-    /// * `&mut self` - mutable reference to the transaction
+    /// * `self` - the transaction, consumed by the commit
     ///
     ///# Returns
     ///
@@ -596,7 +1414,9 @@ impl<'a> Transaction<'a> {
     ///
     // Note: do not use reference to mutable here
     pub fn commit(mut self) -> Result<(), ApplicationError> {
-        self.db.commit_transaction()?;
+        if let TransactionDb::Owned(ref mut db) = self.db {
+            db.commit_transaction()?;
+        }
         self.completed = true;
         Ok(())
     }
@@ -618,12 +1438,380 @@ impl<'a> Drop for Transaction<'a> {
     ///
     /// * `&mut self` - mutable reference to the transaction
     ///
-    ///# Returns
-    ///
-    ///
     fn drop(&mut self) {
         if !self.completed {
-            let _ = self.db.rollback_transaction();
+            if let TransactionDb::Owned(ref mut db) = self.db {
+                let _ = db.rollback_transaction();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_database() -> Box<dyn DatabaseManager> {
+        Box::new(InMemoryDatabase::new())
+    }
+
+    fn test_client(name: &str) -> Client {
+        Client::new(0, name.to_string(), 1, 1)
+    }
+
+    fn admin_session() -> Session {
+        Session::new(&Employee::from_stored(100, "Admin", "hash", EmployeeRole::Admin))
+    }
+
+    #[test]
+    fn transaction_commit_keeps_changes() {
+        let mut db = test_database();
+        {
+            let mut transaction = Transaction::new(&mut db).unwrap();
+            assert!(transaction.db.in_transaction());
+            transaction.db.new_client(&test_client("Ada")).unwrap();
+            transaction.commit().unwrap();
+        }
+        assert!(!db.in_transaction());
+        assert_eq!(db.get_clients().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn transaction_explicit_rollback_discards_changes() {
+        let db = test_database();
+        let mut handle = db.begin_transaction().unwrap();
+        handle.new_client(&test_client("Ada")).unwrap();
+        // the changes are only visible through the transaction handle
+        assert_eq!(handle.get_clients().unwrap().len(), 1);
+        assert!(db.get_clients().unwrap().is_empty());
+        handle.rollback_transaction().unwrap();
+        assert!(!handle.in_transaction());
+        assert!(db.get_clients().unwrap().is_empty());
+        // the handle's transaction is finished
+        assert!(handle.rollback_transaction().is_err());
+    }
+
+    #[test]
+    fn transaction_drop_rolls_back() {
+        let mut db = test_database();
+        {
+            let mut transaction = Transaction::new(&mut db).unwrap();
+            transaction.db.new_client(&test_client("Ada")).unwrap();
+        }
+        assert!(db.get_clients().unwrap().is_empty());
+        // the dropped transaction no longer blocks a new one
+        let transaction = Transaction::new(&mut db).unwrap();
+        transaction.commit().unwrap();
+    }
+
+    #[test]
+    fn transaction_joins_open_transaction() {
+        let db = test_database();
+        let mut handle = db.begin_transaction().unwrap();
+        {
+            let mut inner = Transaction::new(&mut handle).unwrap();
+            assert!(matches!(inner.db, TransactionDb::Joined(_)));
+            inner.db.new_client(&test_client("Ada")).unwrap();
+            // the joined transaction's commit leaves the outer one open
+            inner.commit().unwrap();
+        }
+        assert!(handle.in_transaction());
+        {
+            let mut inner = Transaction::new(&mut handle).unwrap();
+            inner.db.new_client(&test_client("Grace")).unwrap();
+            // nor does dropping it roll the outer one back
         }
+        assert!(handle.in_transaction());
+        assert_eq!(handle.get_clients().unwrap().len(), 2);
+        handle.rollback_transaction().unwrap();
+        assert!(db.get_clients().unwrap().is_empty());
+    }
+
+    #[test]
+    fn transaction_is_not_shared_by_clones() {
+        let db = test_database();
+        let handle = db.begin_transaction().unwrap();
+        assert!(!db.in_transaction());
+        assert!(handle.in_transaction());
+        assert!(!handle.clone_box().in_transaction());
+        // only one transaction may be open on the in-memory state
+        assert!(db.begin_transaction().is_err());
+    }
+
+    #[test]
+    fn client_handler_round_trip() {
+        let db = test_database();
+        let session = admin_session();
+        let mut handler = ClientHandler::new(db.clone_box()).unwrap();
+
+        let client_id = handler.new_client(&session, &test_client("Ada")).unwrap();
+        assert_eq!(handler.get_client(client_id).unwrap().get_client_name(), "Ada");
+
+        let mut client = handler.get_client(client_id).unwrap().clone();
+        client.change_client_service(2);
+        client.change_client_employee_pair(2);
+        handler.update_client(&session, &client).unwrap();
+
+        // a new handler loads the same client back out of the database
+        let reloaded = ClientHandler::new(db.clone_box()).unwrap();
+        assert_eq!(reloaded.get_client(client_id).unwrap(), &client);
+        assert_eq!(reloaded.client_count(), 1);
+
+        handler.remove_client(&session, &client).unwrap();
+        assert!(handler.get_client(client_id).is_err());
+        assert!(db.get_clients().unwrap().is_empty());
+        // every change was audited
+        assert_eq!(db.get_client_history(&HistoryFilter::default()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn update_client_reads_previous_values_from_the_database() {
+        let db = test_database();
+        let session = admin_session();
+        let mut handler = ClientHandler::new(db.clone_box()).unwrap();
+        let client_id = handler.new_client(&session, &test_client("Ada")).unwrap();
+
+        // another session changes the service, this handler's cache still has service 1
+        let mut other = ClientHandler::new(db.clone_box()).unwrap();
+        let mut changed = other.get_client(client_id).unwrap().clone();
+        changed.change_client_service(2);
+        other.update_client(&session, &changed).unwrap();
+
+        let mut client = handler.get_client(client_id).unwrap().clone();
+        assert_eq!(client.get_client_service(), 1);
+        client.change_client_employee_pair(2);
+        handler.update_client(&session, &client).unwrap();
+
+        // the reassignment, and the service change back from the stored value, are recorded
+        let history = db.get_client_history(&HistoryFilter::default()).unwrap();
+        let service_changes: Vec<(i32, i32)> = history
+            .iter()
+            .filter(|entry| entry.action == HistoryAction::ServiceChange)
+            .map(|entry| (entry.old_value, entry.new_value))
+            .collect();
+        assert_eq!(service_changes, vec![(1, 2), (2, 1)]);
+        assert_eq!(db.get_client(client_id).unwrap().unwrap(), client);
+
+        // a client removed elsewhere cannot be updated
+        other.remove_client(&session, &client).unwrap();
+        assert!(matches!(
+            handler.update_client(&session, &client),
+            Err(ApplicationError::NoMatchError(_))
+        ));
+    }
+
+    #[test]
+    fn new_clients_are_removed_by_their_own_ids() {
+        let db = test_database();
+        let session = admin_session();
+        let mut handler = ClientHandler::new(db.clone_box()).unwrap();
+        let ada_id = handler.new_client(&session, &test_client("Ada")).unwrap();
+        let grace_id = handler.new_client(&session, &test_client("Grace")).unwrap();
+        assert_ne!(ada_id, 0);
+        assert_ne!(ada_id, grace_id);
+        assert_eq!(handler.client_count(), 2);
+
+        // the offboarded client is removed, not whichever was cached under 0
+        let ada = handler.get_client(ada_id).unwrap().clone();
+        handler.remove_client(&session, &ada).unwrap();
+        assert!(handler.get_client(ada_id).is_err());
+        assert_eq!(handler.get_client(grace_id).unwrap().get_client_name(), "Grace");
+        let stored = db.get_clients().unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].get_client_id(), grace_id);
+    }
+
+    #[test]
+    fn client_handler_batch_rolls_back() {
+        let db = test_database();
+        let session = admin_session();
+        let mut handler = ClientHandler::new(db.clone_box()).unwrap();
+
+        let result = handler.batch(|handler| {
+            handler.new_client(&session, &test_client("Ada"))?;
+            handler.new_client(&session, &test_client("Grace"))?;
+            Err(ApplicationError::InputError("stop".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(handler.client_count(), 0);
+        assert!(db.get_clients().unwrap().is_empty());
+
+        handler
+            .batch(|handler| {
+                handler.new_client(&session, &test_client("Ada"))?;
+                handler.new_client(&session, &test_client("Grace"))?;
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(handler.client_count(), 2);
+        assert_eq!(db.get_clients().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn employee_handler_round_trip() {
+        let db = test_database();
+        let session = admin_session();
+        let mut handler = EmployeeHandler::new(db.clone_box()).unwrap();
+
+        let employee = Employee::from_stored(0, "Alan", "hash", EmployeeRole::Advisor);
+        let employee_id = handler.add_new_employee(&session, &employee).unwrap();
+        assert_eq!(employee_id, 1);
+
+        // a new handler loads the same employee back out of the database
+        let mut reloaded = EmployeeHandler::new(db.clone_box()).unwrap();
+        let stored = reloaded.get_employee(employee_id).unwrap().unwrap();
+        assert_eq!(stored.get_employee_name(), "Alan");
+        assert_eq!(stored.get_employee_role(), EmployeeRole::Advisor);
+        assert_eq!(reloaded.get_employee_hash(employee_id).unwrap().unwrap(), "hash");
+
+        let mut renamed = stored.clone();
+        renamed.set_employee_name("Alan Turing");
+        handler.modify_employee(&session, &renamed).unwrap();
+        let stored = db.get_employee(employee_id).unwrap().unwrap();
+        assert_eq!(stored.get_employee_name(), "Alan Turing");
+
+        let clients = ClientHandler::new(db.clone_box()).unwrap();
+        handler.delete_employee(&session, employee_id, &clients).unwrap();
+        assert!(!handler.is_valid_employee_id(employee_id).unwrap());
+        assert!(db.get_employee(employee_id).unwrap().is_none());
+    }
+
+    #[test]
+    fn delete_employee_with_clients_is_refused() {
+        let db = test_database();
+        let session = admin_session();
+        let mut employees = EmployeeHandler::new(db.clone_box()).unwrap();
+        let mut clients = ClientHandler::new(db.clone_box()).unwrap();
+        let employee = Employee::from_stored(0, "Alan", "hash", EmployeeRole::Advisor);
+        let employee_id = employees.add_new_employee(&session, &employee).unwrap();
+        clients
+            .new_client(&session, &Client::new(0, "Ada".to_string(), 1, employee_id))
+            .unwrap();
+
+        assert!(employees.delete_employee(&session, employee_id, &clients).is_err());
+        assert!(db.get_employee(employee_id).unwrap().is_some());
+    }
+
+    #[test]
+    fn reassign_and_delete_employee_is_one_transaction() {
+        let db = test_database();
+        let session = admin_session();
+        let mut employees = EmployeeHandler::new(db.clone_box()).unwrap();
+        let mut clients = ClientHandler::new(db.clone_box()).unwrap();
+        let leaving = employees
+            .add_new_employee(&session, &Employee::from_stored(0, "Alan", "hash", EmployeeRole::Advisor))
+            .unwrap();
+        let staying = employees
+            .add_new_employee(&session, &Employee::from_stored(0, "Grace", "hash", EmployeeRole::Advisor))
+            .unwrap();
+        for name in &["Ada", "Edsger"] {
+            clients
+                .new_client(&session, &Client::new(0, name.to_string(), 1, leaving))
+                .unwrap();
+        }
+
+        // the deletion fails after the clients were reassigned, so nothing is kept
+        let self_delete =
+            Session::new(&Employee::from_stored(leaving, "Alan", "hash", EmployeeRole::Admin));
+        assert!(employees
+            .reassign_and_delete_employee(&self_delete, leaving, staying, &mut clients)
+            .is_err());
+        assert!(db.get_clients().unwrap().iter().all(|client| client.get_asn_employee() == leaving));
+        assert_eq!(clients.get_clients_for_employee(leaving).unwrap().len(), 2);
+        assert!(db.get_employee(leaving).unwrap().is_some());
+        assert!(employees.is_valid_employee_id(leaving).unwrap());
+
+        let reassigned = employees
+            .reassign_and_delete_employee(&session, leaving, staying, &mut clients)
+            .unwrap();
+        assert_eq!(reassigned, 2);
+        assert!(db.get_clients().unwrap().iter().all(|client| client.get_asn_employee() == staying));
+        assert_eq!(clients.get_clients_for_employee(staying).unwrap().len(), 2);
+        assert!(db.get_employee(leaving).unwrap().is_none());
+        assert!(!employees.is_valid_employee_id(leaving).unwrap());
+    }
+
+    #[test]
+    fn bootstrap_admin_only_until_an_admin_exists() {
+        let db = test_database();
+        let mut handler = EmployeeHandler::new(db.clone_box()).unwrap();
+        assert!(!handler.has_admin().unwrap());
+        let advisor = Employee::from_stored(0, "Alan", "hash", EmployeeRole::Advisor);
+        assert!(matches!(
+            handler.bootstrap_admin(&advisor),
+            Err(ApplicationError::PermissionError(_))
+        ));
+
+        let admin = Employee::from_stored(0, "Grace", "hash", EmployeeRole::Admin);
+        let admin_id = handler.bootstrap_admin(&admin).unwrap();
+        assert!(handler.has_admin().unwrap());
+        let stored = handler.get_employee(admin_id).unwrap().unwrap();
+        assert_eq!(stored.get_employee_role(), EmployeeRole::Admin);
+
+        let second = Employee::from_stored(0, "Ada", "hash", EmployeeRole::Admin);
+        assert!(matches!(
+            handler.bootstrap_admin(&second),
+            Err(ApplicationError::PermissionError(_))
+        ));
+        assert_eq!(handler.get_employees().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn modify_employee_is_admin_only() {
+        let db = test_database();
+        let mut handler = EmployeeHandler::new(db.clone_box()).unwrap();
+        let employee = Employee::new(0, "Alan", "Enigma1912", EmployeeRole::Advisor).unwrap();
+        let employee_id = handler.add_new_employee(&admin_session(), &employee).unwrap();
+        let mut employee = handler.get_employee(employee_id).unwrap().unwrap();
+        let own_session = Session::new(&employee);
+
+        // not even an employee's own password may be changed through modify_employee
+        employee.change_password("Bombe19391").unwrap();
+        assert!(matches!(
+            handler.modify_employee(&own_session, &employee),
+            Err(ApplicationError::PermissionError(_))
+        ));
+    }
+
+    #[test]
+    fn change_own_password_verifies_current_password() {
+        let db = test_database();
+        let mut handler = EmployeeHandler::new(db.clone_box()).unwrap();
+        let employee = Employee::new(0, "Alan", "Enigma1912", EmployeeRole::Advisor).unwrap();
+        let employee_id = handler.add_new_employee(&admin_session(), &employee).unwrap();
+        let session = Session::new(&handler.get_employee(employee_id).unwrap().unwrap());
+
+        assert!(matches!(
+            handler.change_own_password(&session, "wrong", "Bombe19391"),
+            Err(ApplicationError::PassValidationError(_))
+        ));
+        // the new password must meet the PasswordPolicy
+        assert!(handler.change_own_password(&session, "Enigma1912", "short").is_err());
+        assert!(handler
+            .change_own_password(&session, "Enigma1912", "Enigma1912")
+            .is_err());
+
+        handler
+            .change_own_password(&session, "Enigma1912", "Bombe19391")
+            .unwrap();
+        let stored = db.get_employee(employee_id).unwrap().unwrap();
+        assert!(Authenticator::verify_password(stored.get_employee_hash(), "Bombe19391"));
+        assert_eq!(stored.get_employee_role(), EmployeeRole::Advisor);
+    }
+
+    #[test]
+    fn update_password_hash_keeps_name_and_role() {
+        let db = test_database();
+        let mut handler = EmployeeHandler::new(db.clone_box()).unwrap();
+        let employee = Employee::from_stored(0, "Alan", "old hash", EmployeeRole::Supervisor);
+        let employee_id = handler.add_new_employee(&admin_session(), &employee).unwrap();
+
+        handler.update_password_hash(employee_id, "new hash").unwrap();
+        let stored = db.get_employee(employee_id).unwrap().unwrap();
+        assert_eq!(stored.get_employee_hash(), "new hash");
+        assert_eq!(stored.get_employee_name(), "Alan");
+        assert_eq!(stored.get_employee_role(), EmployeeRole::Supervisor);
+        assert_eq!(handler.get_employee_hash(employee_id).unwrap().unwrap(), "new hash");
+        assert!(handler.update_password_hash(employee_id + 1, "hash").is_err());
     }
 }
@@ -10,7 +10,7 @@
 use crate::errors::ApplicationError;
 use log::{error, warn};
 
-use std::io::{self, Write};
+use std::io::{self, IsTerminal, Write};
 
 //
 // ********************************************
@@ -57,6 +57,12 @@ pub fn get_integer_input() -> Result<i32, ApplicationError> {
 
         // begins by reading line into mut& input_string for matching
         match std::io::stdin().read_line(&mut input_string) {
+            // stdin closed (end of piped input), retrying would loop forever
+            Ok(0) => {
+                return Err(ApplicationError::InputError(
+                    "No more input available".to_string(),
+                ));
+            }
             Ok(_) => {
                 // input successful, assign it place of Ok(wildcard)
                 // attempt parse/trim input to 64bit integer
@@ -86,75 +92,116 @@ pub fn get_integer_input() -> Result<i32, ApplicationError> {
     }
 }
 
-/**
-* Checks to see if the provided input string contains
-* invalid characters
-*@return: boolean - input contains invalid chars - true or false
-*/
-
-/// invalid character detection function
+/// Gets a password input from the user, without echoing it
 ///
-/// compares each character in provided input string to the
-/// list of invalid characters, if any match, returns true.
+/// Continues looping until the user provides a non-empty password.
+/// No characters are rejected, symbols such as `!@#$%` are valid
+/// password characters. Surrounding whitespace is trimmed, as the
+/// login prompt always did, the same way for new passwords and logins.
+/// On a terminal, echo is disabled while the password is typed. When
+/// stdin is not a terminal (input piped in by a script, or tests), the
+/// line is read from stdin as is.
 ///
 ///# Arguments
 ///
-///* 'input' - reference to the string input
+///* 'prompt' - the text displayed before reading the password
 ///
 ///# Returns
 ///
-///* 'boolean' - true / false result of .contains() comparison
+///* 'String' - user input password, trimmed of surrounding whitespace
+///
+///# Errors
 ///
-fn invalid_input_chars(input: &str) -> bool {
-    let invalid_characters = r#"\!$@()#%^&*<>/"\,.|;~`:' "#;
-    // handled with any() into closure(arg c).contains(c) to validate
-    input.chars().any(|c| invalid_characters.contains(c))
+///* 'ApplicationError::IoError' - failure to read from the terminal / stdin, or to
+///  disable echo on the terminal
+///* 'ApplicationError::InputError' - stdin was closed before a password was entered
+///
+pub fn get_password_input(prompt: &str) -> Result<String, ApplicationError> {
+    loop {
+        let password = if io::stdin().is_terminal() {
+            read_hidden_line(prompt)?
+        } else {
+            read_visible_line(prompt)?
+        };
+        // spaces inside the password are kept, only surrounding whitespace is removed
+        let password = password.trim();
+        if password.is_empty() {
+            println!("\nPassword cannot be empty. Please try again.");
+        } else {
+            return Ok(password.to_string());
+        }
+    }
+}
+
+/// reads a line from the terminal with echo disabled
+///
+/// only used when stdin is a terminal. When echo cannot be disabled the
+/// error is returned, the password is never read with echo on a terminal.
+fn read_hidden_line(prompt: &str) -> Result<String, ApplicationError> {
+    rpassword::prompt_password(format!("{}: ", prompt)).map_err(|e| {
+        error!("Could not read the password with echo disabled: {:?}", e);
+        ApplicationError::IoError(e)
+    })
+}
+
+/// reads a line from stdin as is, used when stdin is not a terminal
+fn read_visible_line(prompt: &str) -> Result<String, ApplicationError> {
+    print!("{}: ", prompt);
+    io::stdout().flush().map_err(ApplicationError::IoError)?;
+    let mut user_input = String::new();
+    match io::stdin().read_line(&mut user_input) {
+        Ok(0) => Err(ApplicationError::InputError(
+            "No more input available".to_string(),
+        )),
+        Ok(_) => Ok(user_input),
+        Err(e) => {
+            error!("Error, could not read input: {:?}", e);
+            Err(ApplicationError::IoError(e))
+        }
+    }
 }
 
-/// Gets a valid string input from the user
+/// Gets a free form line of text input from the user
 ///
-/// Continues looping until the user provides a valid,
-/// non-empty string.
+/// Continues looping until the user provides a non-empty line.
+/// No characters are rejected, so this is used for inputs
+/// such as file paths and full names.
 ///
-///# Returns
+///# Arguments
 ///
-///* 'String' - user input string value
+///* 'prompt' - the text displayed before reading the input
 ///
-///# Behavior
+///# Returns
 ///
-/// 1. prompts for a user input
-/// 2. reads in user input str
-/// 3. remove / trim any unused whitespace chars
-/// 4. perform non-empty validation step
-/// 5. perform invalid chars validation step
-/// 6. so long as input valid, return input as string
-/// 7. else invalid input, give msg/reason, & restart loop
+///* 'String' - user input string value, trimmed of surrounding whitespace
 ///
-///# Notes
-/// - gracefully handles errors that may arise during string input process
+///# Errors
+///
+///* 'ApplicationError::IoError' - failure to read from stdin / flush stdout
+///* 'ApplicationError::InputError' - stdin was closed before a line was entered
 ///
-pub fn get_string_input() -> Result<String, ApplicationError> {
+pub fn get_text_input(prompt: &str) -> Result<String, ApplicationError> {
     loop {
         let mut user_input = String::new();
-        print!("Enter your password input: ");
-        // flushes console out to make sure request displays
-        io::stdout()
-            .flush()
-            .map_err(|e| ApplicationError::IoError(e))?;
-        // read input into user_input
+        print!("{}: ", prompt);
+        io::stdout().flush().map_err(ApplicationError::IoError)?;
+
         match io::stdin().read_line(&mut user_input) {
+            Ok(0) => {
+                return Err(ApplicationError::InputError(
+                    "No more input available".to_string(),
+                ));
+            }
             Ok(_) => {
-                // read is ok, using wildcard placeholder
                 let trimmed = user_input.trim();
                 if trimmed.is_empty() {
                     println!("\nInput cannot be empty. Please try again.");
-                } else if invalid_input_chars(trimmed) {
-                    println!("\nInput contains invalid characters. Please try again.");
                 } else {
                     return Ok(trimmed.to_string());
                 }
             }
             Err(e) => {
+                error!("Error, could not read input: {:?}", e);
                 return Err(ApplicationError::IoError(e));
             }
         }